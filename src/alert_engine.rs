@@ -0,0 +1,124 @@
+/*!
+ * alert_engine.rs — Stateful, hysteresis-aware alert engine
+ *
+ * Per-snapshot alert functions (see `alert_on_fg_waits` et al.) are
+ * stateless: a metric hovering right at its threshold fires and clears on
+ * every snapshot. This module tracks state across a *series* of snapshots
+ * so flapping metrics only report an "entered"/"recovered" transition
+ * instead of repeating on every read.
+ *
+ * Modeled on collectd's threshold plugin: once a (metric, object) pair
+ * trips into an alert state it stays there until the value recovers past
+ * `threshold - hysteresis`, and `persist` requires the new state to hold
+ * for N consecutive snapshots before it is ever reported.
+ *
+ * Co-developed by Laurence Oberman and ChatGPT (OpenAI), 2025.
+ * License: GPLv3+
+ */
+
+use std::collections::HashMap;
+
+use crate::thresholds::{Severity, Tier};
+
+/// Ok, or alerting at a given severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertState {
+    Ok,
+    Alert(Severity),
+}
+
+/// A reported state transition for one (metric, object) pair.
+#[derive(Debug, Clone)]
+pub enum AlertEvent {
+    Entered { metric: String, object: String, severity: Severity, value: f64 },
+    Recovered { metric: String, object: String, value: f64 },
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    // Last state actually reported to the caller.
+    state: AlertState,
+    // Raw state the current snapshot run has been computing, and for how
+    // many consecutive snapshots — used to implement `persist`.
+    candidate: AlertState,
+    candidate_run: u32,
+}
+
+impl Default for Slot {
+    fn default() -> Self {
+        Slot { state: AlertState::Ok, candidate: AlertState::Ok, candidate_run: 0 }
+    }
+}
+
+/// Tracks per-(metric, object) alert state across a series of AWR snapshots.
+#[derive(Debug, Clone, Default)]
+pub struct AlertEngine {
+    slots: HashMap<(String, String), Slot>,
+}
+
+impl AlertEngine {
+    pub fn new() -> Self {
+        AlertEngine::default()
+    }
+
+    /// Feed one metric reading for one snapshot and get back an event only
+    /// when the *reported* state actually changes.
+    ///
+    /// `tier`/`hysteresis`/`persist` should come from the `AlertThresholds`
+    /// in effect for `object` (e.g. via `AlertThresholds::for_object`).
+    pub fn evaluate(
+        &mut self,
+        metric: &str,
+        object: &str,
+        value: f64,
+        tier: Tier,
+        hysteresis: f64,
+        persist: u32,
+    ) -> Option<AlertEvent> {
+        let key = (metric.to_string(), object.to_string());
+        let slot = self.slots.entry(key).or_default();
+
+        let raw = match tier.check(value) {
+            Some(sev) => AlertState::Alert(sev),
+            None => AlertState::Ok,
+        };
+
+        // Escalating to a higher (or equal) severity always takes effect
+        // immediately; hysteresis only slows down the climb *back* to Ok.
+        let candidate = match slot.state {
+            AlertState::Ok => raw,
+            AlertState::Alert(held) => {
+                let escalating = matches!(raw, AlertState::Alert(sev) if sev >= held);
+                if escalating {
+                    raw
+                } else {
+                    let boundary = tier.for_severity(held);
+                    if value <= boundary - hysteresis {
+                        raw
+                    } else {
+                        AlertState::Alert(held)
+                    }
+                }
+            }
+        };
+
+        if candidate == slot.candidate {
+            slot.candidate_run += 1;
+        } else {
+            slot.candidate = candidate;
+            slot.candidate_run = 1;
+        }
+
+        if slot.candidate_run < persist.max(1) || candidate == slot.state {
+            return None;
+        }
+
+        slot.state = candidate;
+        Some(match candidate {
+            AlertState::Alert(severity) => {
+                AlertEvent::Entered { metric: metric.to_string(), object: object.to_string(), severity, value }
+            }
+            AlertState::Ok => AlertEvent::Recovered { metric: metric.to_string(), object: object.to_string(), value },
+        })
+    }
+}
@@ -0,0 +1,182 @@
+/*!
+ * health.rs — Aggregate severity scoring and overall health verdict
+ *
+ * The per-section alert lists `analyze`/`export` produce are flat: there's
+ * no single number telling an operator how bad a snapshot is relative to
+ * another, which matters once you're triaging reports from many nodes.
+ * Every alert earns `severity_weight(severity) * category_weight(rule's
+ * category)` points; the sum across all sections is the report's total
+ * score, compared against `verdict_degraded_score` / `verdict_critical_score`
+ * for a Healthy/Degraded/Critical verdict, alongside the highest-scoring
+ * individual alerts as the "top contributing factors".
+ *
+ * Co-developed by Laurence Oberman and ChatGPT (OpenAI), 2025.
+ * License: GPLv3+
+ */
+
+use serde::Serialize;
+
+use crate::report::{AlertSeverity, Section};
+use crate::thresholds::AlertThresholds;
+
+/// Broad grouping of what kind of problem a rule id represents, so its
+/// contribution to the overall score can be weighted independently of
+/// severity (e.g. a RAC/GC alert might matter more than a throughput
+/// anomaly at the same severity).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Latency,
+    Redo,
+    RacGc,
+    Concurrency,
+    Throughput,
+    Other,
+}
+
+fn category_label(c: Category) -> &'static str {
+    match c {
+        Category::Latency => "I/O Latency",
+        Category::Redo => "Redo",
+        Category::RacGc => "RAC/GC",
+        Category::Concurrency => "Concurrency",
+        Category::Throughput => "Throughput Anomaly",
+        Category::Other => "Other",
+    }
+}
+
+fn severity_label(s: AlertSeverity) -> &'static str {
+    match s {
+        AlertSeverity::Info => "INFO",
+        AlertSeverity::Warn => "WARN",
+        AlertSeverity::Critical => "CRITICAL",
+    }
+}
+
+/// Maps a rule id (see the "HOW TO ADD A NEW ALERT TYPE" walkthrough in
+/// main.rs) to the category it contributes to. An id this doesn't
+/// recognize yet (e.g. a freshly added alert) falls back to
+/// `Category::Other` rather than panicking.
+fn category_for_rule(rule_id: u32) -> Category {
+    match rule_id {
+        2 | 4 | 9 | 20 | 28 => Category::Latency,
+        10 | 21 | 23 => Category::Redo,
+        14 => Category::RacGc,
+        11 | 13 | 15 | 22 => Category::Concurrency,
+        1 | 8 | 12 | 17 | 19 | 24 => Category::Throughput,
+        _ => Category::Other,
+    }
+}
+
+fn severity_weight(t: &AlertThresholds, sev: AlertSeverity) -> f64 {
+    match sev {
+        AlertSeverity::Info => t.severity_weight_info,
+        AlertSeverity::Warn => t.severity_weight_warning,
+        AlertSeverity::Critical => t.severity_weight_critical,
+    }
+}
+
+fn category_weight(t: &AlertThresholds, cat: Category) -> f64 {
+    match cat {
+        Category::Latency => t.category_weight_latency,
+        Category::Redo => t.category_weight_redo,
+        Category::RacGc => t.category_weight_racgc,
+        Category::Concurrency => t.category_weight_concurrency,
+        Category::Throughput => t.category_weight_throughput,
+        Category::Other => t.category_weight_other,
+    }
+}
+
+/// Overall verdict for a report, from `verdict_degraded_score` /
+/// `verdict_critical_score` in `AlertThresholds`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Healthy,
+    Degraded,
+    Critical,
+}
+
+impl Verdict {
+    pub fn label(self) -> &'static str {
+        match self {
+            Verdict::Healthy => "Healthy",
+            Verdict::Degraded => "Degraded",
+            Verdict::Critical => "Critical",
+        }
+    }
+}
+
+/// One alert's contribution to the total score, kept for the "top
+/// contributing factors" list.
+#[derive(Debug, Clone, Serialize)]
+pub struct Factor {
+    pub event: String,
+    pub category: Category,
+    pub severity: AlertSeverity,
+    pub points: f64,
+}
+
+/// The rolled-up score for a report: total points, the resulting
+/// verdict, and the highest-scoring individual alerts.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthScore {
+    pub total_score: f64,
+    pub verdict: Verdict,
+    pub top_factors: Vec<Factor>,
+}
+
+const MAX_TOP_FACTORS: usize = 5;
+
+/// Scores every alert across `sections` and rolls it up into one
+/// `HealthScore`.
+pub fn score(sections: &[Section], t: &AlertThresholds) -> HealthScore {
+    let mut factors: Vec<Factor> = sections
+        .iter()
+        .flat_map(|s| &s.alerts)
+        .map(|a| {
+            let category = category_for_rule(a.rule_id);
+            let points = severity_weight(t, a.severity) * category_weight(t, category);
+            Factor { event: a.event.clone(), category, severity: a.severity, points }
+        })
+        .collect();
+
+    let total_score: f64 = factors.iter().map(|f| f.points).sum();
+
+    let verdict = if total_score >= t.verdict_critical_score {
+        Verdict::Critical
+    } else if total_score >= t.verdict_degraded_score {
+        Verdict::Degraded
+    } else {
+        Verdict::Healthy
+    };
+
+    factors.sort_by(|a, b| b.points.partial_cmp(&a.points).unwrap_or(std::cmp::Ordering::Equal));
+    factors.truncate(MAX_TOP_FACTORS);
+
+    HealthScore { total_score, verdict, top_factors: factors }
+}
+
+/// Renders a `HealthScore` as the markdown block `render_text` prints
+/// ahead of the Knowledge Base section.
+pub fn render_summary(score: &HealthScore) -> String {
+    let mut out = format!("## Overall I/O Health: {} (score {:.1})\n\n", score.verdict.label(), score.total_score);
+
+    if score.top_factors.is_empty() {
+        out.push_str("No alerts contributed to the score for this snapshot.\n\n");
+    } else {
+        out.push_str("Top contributing factors:\n");
+        for f in &score.top_factors {
+            out.push_str(&format!(
+                "- [{}] {} — {} ({:.1} pts)\n",
+                severity_label(f.severity),
+                category_label(f.category),
+                f.event,
+                f.points
+            ));
+        }
+        out.push('\n');
+    }
+
+    out
+}
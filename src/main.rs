@@ -6,22 +6,46 @@
  * actionable expert comments and problem alerts underneath.
  *
  * Usage:
- *   awr_io_analyze <awrrpt_xxx.txt> [config.toml]
+ *   awr_io_analyze analyze <awrrpt_xxx.txt> [--config <path>] [--format text|json]
+ *   awr_io_analyze diff <old.txt> <new.txt> [<newer.txt> ...] [--config <path>] [--format text|json]
+ *   awr_io_analyze export <awrrpt_xxx.txt> --format json|prometheus --out <outfile> [--config <path>]
+ *   awr_io_analyze thresholds show [--config <path>]
+ *   awr_io_analyze thresholds init [<path>]
  *
  * - Reads any Oracle AWR text report (plain or HTML-to-txt)
- * - Extracts and prints the three key I/O tables, as formatted in the report:
+ * - `analyze` extracts and prints the key I/O tables, as formatted in the
+ *   report:
  *      1. Top 10 Foreground Events by Total Wait Time
- *      2. Wait Classes by Total Wait Time
- *      3. IO Profile
- * - Under each table: prints contextual alerts if thresholds are exceeded
- * - Ends with a mini Knowledge Base / Best Practices for quick reference
+ *      2. Top 10 Background Events by Total Wait Time (skipped if the
+ *         report has no such section)
+ *      3. Wait Classes by Total Wait Time
+ *      4. IO Profile
+ *      5. Tablespace IO Stats (per-tablespace latency, checked against any
+ *         matching `[tablespace.NAME]` override — see thresholds.rs)
+ *
+ *   plus contextual alerts under each table when thresholds are exceeded,
+ *   an aggregate "Overall I/O Health" verdict (Healthy/Degraded/Critical)
+ *   with the top contributing alerts (see health.rs), and a mini
+ *   Knowledge Base / Best Practices section at the end.
+ *   `--format json` emits the same analysis, plus the health score, as
+ *   structured JSON instead of markdown, for feeding dashboards/databases/
+ *   other tooling.
+ * - `diff` takes several time-ordered per-node reports instead of one and
+ *   flags metrics that climb across the whole window (see trend.rs)
+ * - `export` writes the extracted metrics to a file as the same
+ *   structured JSON `analyze --format json` would print, or as
+ *   Prometheus exposition text with a per-event latency histogram (see
+ *   prometheus.rs)
+ * - `thresholds show` prints the resolved thresholds as JSON; `thresholds
+ *   init` writes a fully-commented default config so you don't have to
+ *   hand-build one from the docs
  *
  * Co-developed by Laurence Oberman and ChatGPT (OpenAI), 2025.
  * License: GPLv3+
  */
 
 // Increment as tool evolves
-const VERSION_NUMBER: &str = "1.1.0";
+const VERSION_NUMBER: &str = "1.2.0";
 
 /*
 Major Foreground & Background Wait Events
@@ -54,13 +78,18 @@ checkpoint completed
 
    Step 1 — Add a new field to AlertThresholds in thresholds.rs:
 
-       pub db_cpu_pct: f64,
+       pub db_cpu_pct: Tier,
 
    Step 2 — Add default & config value in `impl Default`:
-       db_cpu_pct: 80.0,
+       db_cpu_pct: Tier::new(50.0, 80.0, 95.0),
+
+   For the TOML keys to be read, also add db_cpu_pct_info / _warning /
+   _critical to RawThresholds (and its Default), build the Tier in
+   AlertThresholds::from_raw, and list the keys in KNOWN_KEYS.
 
    Also add this to your awr_io_analyze.toml:
-       db_cpu_pct = 80.0
+       db_cpu_pct_warning = 80.0
+       db_cpu_pct_critical = 95.0
 
    --------------------------------------------------------------------------
 
@@ -69,16 +98,17 @@ checkpoint completed
    usually as:
         "DB CPU           <value>     <value>      <value>    59.0   "
 
-   Add this inside alert_on_fg_waits(...):
+   Add this inside alert_on_fg_waits(...), picking an unused rule id:
 
        // DB CPU % threshold check
        if row.contains("DB CPU") {
            if let Some(pct) = extract_percent_from_wait_row(row) {
-               if pct > t.db_cpu_pct {
-                   alerts.push(format!(
-                       "🔵 INFO: DB CPU {:.1}% exceeds threshold {}% — CPU-bound workload.",
-                        pct, t.db_cpu_pct
-                   ));
+               if let Some(sev) = t.db_cpu_pct.check(pct) {
+                   let text = format!(
+                       "{} {}: DB CPU {:.1}% exceeds threshold {}% — CPU-bound workload.",
+                       sev.emoji(), sev.label(), pct, t.db_cpu_pct.for_severity(sev)
+                   );
+                   alerts.push(Alert::new(25, sev, "DB CPU", Some(pct), Some(t.db_cpu_pct.for_severity(sev)), text));
                }
            }
        }
@@ -88,12 +118,15 @@ checkpoint completed
    Step 4 — Pass the threshold object into alert_on_fg_waits()
 
    Change function signature from:
-       fn alert_on_fg_waits(table: &[String]) -> Vec<String>
+       fn alert_on_fg_waits(table: &[String]) -> Vec<Alert>
 
    To:
-       fn alert_on_fg_waits(table: &[String], t: &AlertThresholds) -> Vec<String>
+       fn alert_on_fg_waits(table: &[String], t: &AlertThresholds) -> Vec<Alert>
 
-   And update your call site in print_table_with_alert():
+   `Alert` (in report.rs) carries both the rendered text line the markdown
+   output uses and the structured rule id/severity/event/value/threshold a
+   `--format json` consumer reads. The call site in build_section() is
+   unchanged either way:
        let alerts = alert_fn(&table, thresholds);
 
    --------------------------------------------------------------------------
@@ -105,8 +138,26 @@ checkpoint completed
    ============================================================================
 */
 
+mod alert_engine;
+mod health;
+mod prometheus;
+mod report;
+mod threshold_source;
 mod thresholds;
-use thresholds::{AlertThresholds, load_thresholds_from_file};
+mod trend;
+mod units;
+use report::{Alert, Section};
+use threshold_source::ThresholdSource;
+use thresholds::{load_thresholds_from_file, AlertThresholds, Severity};
+
+// `alert_engine::AlertEngine` (hysteresis + persist across a snapshot
+// series) is wired into `trend::analyze`, the multi-snapshot consumer —
+// see `trend.rs`. `analyze`/`export` read thresholds through a
+// `ThresholdSource` for each run; `diff` additionally calls
+// `reload_if_changed()` once per snapshot file it reads, then runs the
+// trend analysis once with the thresholds in effect after the last read,
+// so every snapshot in the window is judged against that same set (see
+// `threshold_source.rs`).
 
 use regex::Regex;
 use std::env;
@@ -121,31 +172,99 @@ use std::process;
 */
 
 
-/// Usage output
-fn usage() {
+/// Top-level usage: lists the subcommands. Each subcommand has its own
+/// `usage_<name>()` with command-specific flags, shown via `<command>
+/// --help` or on a bad invocation of that command.
+fn usage() -> ! {
     eprintln!("
 Oracle AWR I/O Analyzer (Rust)
 ------------------------------
 
 Usage:
-  awr_io_analyze <awrrpt_xxx.txt> [config.toml]
-
-  Note!! This only works with per-node AWR reports not global reports
-  Make sure you ask for per-node AWR reports
-   
-  For the config.toml to override the default the file you create
-  looks like this with your own values replaced.
-  
- # awr_io_analyze.toml — threshold config for AWR I/O Analyzer
-wait_pct = 10.0
-io_latency_ms = 20.0
-row_lock_pct = 3.0
-gc_remote_pct = 2.0
-io_request_rate =10000.0
+  awr_io_analyze <command> [options]
+
+Commands:
+  analyze      Analyze a single AWR report and print findings
+  diff         Compare two or more time-ordered AWR reports and flag regressions
+  export       Write extracted metrics to a file as JSON or Prometheus text
+  thresholds   Show the active thresholds, or write a commented default config
+
+Run 'awr_io_analyze <command> --help' for command-specific options.
+
+Note!! This only works with per-node AWR reports not global reports.
+Make sure you ask for per-node AWR reports.
 
 Developed by Laurence Oberman, assisted by ChatGPT (OpenAI), 2025
 ");
-    println!("Version {}",VERSION_NUMBER);
+    println!("Version {}", VERSION_NUMBER);
+    process::exit(1);
+}
+
+fn usage_analyze() -> ! {
+    eprintln!("
+Usage:
+  awr_io_analyze analyze <awrrpt_xxx.txt> [--config <path>] [--format text|json]
+
+  Extracts and prints the key I/O tables (Foreground Wait Events, Wait
+  Classes, IO Profile, Tablespace IO Stats), with contextual alerts under
+  each table when thresholds are exceeded (Tablespace IO Stats checks
+  against any matching [tablespace.NAME] override), and a mini Knowledge
+  Base / Best Practices section at the end.
+
+  --format json emits the whole analysis (report filename, active
+  thresholds, and each section's raw rows + structured alerts) as JSON
+  instead of markdown. Defaults to --format text.
+
+  --config points at a thresholds TOML file (see 'thresholds init').
+  Defaults to ./awr_io_analyze.toml.
+");
+    process::exit(1);
+}
+
+fn usage_diff() -> ! {
+    eprintln!("
+Usage:
+  awr_io_analyze diff <old.txt> <new.txt> [<newer.txt> ...] [--config <path>] [--format text|json]
+
+  Takes two or more time-ordered per-node AWR reports and, instead of
+  analyzing each in isolation, aligns each metric across all of them and
+  flags ones that keep climbing or grow by more than trend_regression_pct
+  from the first snapshot to the last.
+
+  --config and --format behave as in 'analyze'.
+");
+    process::exit(1);
+}
+
+fn usage_export() -> ! {
+    eprintln!("
+Usage:
+  awr_io_analyze export <awrrpt_xxx.txt> --format json|prometheus --out <outfile> [--config <path>]
+
+  Writes a single report's extracted metrics to <outfile>:
+    --format json         the same structured report 'analyze --format json' prints
+    --format prometheus    Prometheus exposition text (gauges plus a per-event
+                           latency histogram using the histogram_bucket_*
+                           config fields)
+
+  --config behaves as in 'analyze'.
+");
+    process::exit(1);
+}
+
+fn usage_thresholds() -> ! {
+    eprintln!("
+Usage:
+  awr_io_analyze thresholds show [--config <path>]
+  awr_io_analyze thresholds init [<path>]
+
+  'show' prints the active thresholds (defaults merged with the --config
+  file, ./awr_io_analyze.toml if not given) as JSON.
+
+  'init' writes a fully-commented default awr_io_analyze.toml to <path>
+  (default ./awr_io_analyze.toml), generated from AlertThresholds's
+  built-in defaults, so you don't have to hand-build one from the docs.
+");
     process::exit(1);
 }
 
@@ -159,7 +278,7 @@ fn read_lines(path: &str) -> Vec<String> {
 }
 
 /// Extracts native AWR table
-fn extract_native_table(lines: &[String], section_title: &str, max_gap: usize)
+pub fn extract_native_table(lines: &[String], section_title: &str, max_gap: usize)
     -> Option<Vec<String>>
 {
     let section_pat = Regex::new(section_title).unwrap();
@@ -181,6 +300,7 @@ let stop_patterns = vec![
     // *** Add these lines ***
     Regex::new(r"^Wait Classes by Total Wait Time").unwrap(),
     Regex::new(r"^IO Profile").unwrap(),
+    Regex::new(r"^File IO Stats").unwrap(),
 ];
 
     let mut start_idx = None;
@@ -197,6 +317,9 @@ let stop_patterns = vec![
     let mut started = false;
     let mut gap = 0;
 
+    let dash_prefix_re = Regex::new(r"^\s*-\s+").unwrap();
+    let label_line_re = Regex::new(r"^[A-Z][A-Za-z\s]+:$").unwrap();
+
     for l in &lines[start + 1..] {
         let trim = l.trim();
 
@@ -204,9 +327,7 @@ let stop_patterns = vec![
             break;
         }
 
-        if Regex::new(r"^\s*-\s+").unwrap().is_match(l)
-            || Regex::new(r"^[A-Z][A-Za-z\s]+:$").unwrap().is_match(trim)
-        {
+        if dash_prefix_re.is_match(l) || label_line_re.is_match(trim) {
             if started {
                 break;
             } else {
@@ -247,7 +368,7 @@ let stop_patterns = vec![
 }
 
 /// Extracts % DB Time from table rows
-fn extract_percent_from_wait_row(row: &str) -> Option<f64> {
+pub fn extract_percent_from_wait_row(row: &str) -> Option<f64> {
     let parts: Vec<&str> = row.split_whitespace().collect();
     if parts.len() < 2 {
         return None;
@@ -272,7 +393,7 @@ fn extract_percent_from_wait_row(row: &str) -> Option<f64> {
      3252.59ms → 3252.59ms
    ============================================================================
 */
-fn extract_latency_ms(row: &str) -> Option<f64> {
+pub fn extract_latency_ms(row: &str) -> Option<f64> {
     let re = Regex::new(r"(\d*\.?\d+)(ms|us)").unwrap();
 
     if let Some(cap) = re.captures(row) {
@@ -288,7 +409,7 @@ fn extract_latency_ms(row: &str) -> Option<f64> {
     None
 }
 
-fn extract_event_name(row: &str) -> String {
+pub fn extract_event_name(row: &str) -> String {
     // Normalize all whitespace: tabs and weird unicode spaces from HTML->txt
     let mut clean = row.replace('\t', " ");
     for ws in ['\u{00A0}', '\u{2007}', '\u{202F}'] {
@@ -333,48 +454,76 @@ fn extract_event_name(row: &str) -> String {
    ALERT LOGIC: Now expanded for nearly all Top 20 rules!
    ======================================================================== */
 
-fn alert_on_fg_waits(table: &[String], t: &AlertThresholds) -> Vec<String> {
+fn alert_on_fg_waits(table: &[String], t: &AlertThresholds) -> Vec<Alert> {
     let mut alerts = Vec::new();
 
     for row in table {
         // [RULE 4] General: high % wait time for any FG event
         if let Some(pct) = extract_percent_from_wait_row(row) {
-            if pct > t.wait_pct {
+            if let Some(sev) = t.wait_pct.check(pct) {
                 let event = extract_event_name(row);
-                alerts.push(format!("🟠 High wait time for event '{}': {:.1}% of DB time.", event, pct));
+                let text = format!(
+                    "{} {}: High wait time for event '{}': {:.1}% of DB time (> {:.1}% threshold).",
+                    sev.emoji(), sev.label(), event, pct, t.wait_pct.for_severity(sev)
+                );
+                alerts.push(Alert::new(4, sev, event, Some(pct), Some(t.wait_pct.for_severity(sev)), text));
             }
         }
 
         // [RULE 2] High I/O latency (any event)
         if let Some(lat) = extract_latency_ms(row) {
             let event = extract_event_name(row);
-            if lat > t.io_latency_ms {
-                alerts.push(format!(
-                    "🔴 High I/O latency for '{}': {:.2}ms (> {}ms threshold).",
-                    event, lat, t.io_latency_ms
-                ));
+            if let Some(sev) = t.io_latency_ms.check(lat) {
+                let text = format!(
+                    "{} {}: High I/O latency for '{}': {:.2}ms (> {:.1}ms threshold).",
+                    sev.emoji(), sev.label(), event, lat, t.io_latency_ms.for_severity(sev)
+                );
+                alerts.push(Alert::new(2, sev, event, Some(lat), Some(t.io_latency_ms.for_severity(sev)), text));
             }
         }
 
         // [RULE 10] log file sync / parallel write
         if row.contains("log file sync") || row.contains("log file parallel write") {
             if let Some(pct) = extract_percent_from_wait_row(row) {
-                if pct > t.wait_pct {
-                    alerts.push(format!("🔴 Redo log bottleneck: '{}' {:.1}% of DB time.", extract_event_name(row), pct));
+                if let Some(sev) = t.wait_pct.check(pct) {
+                    let event = extract_event_name(row);
+                    let text = format!(
+                        "{} {}: Redo log bottleneck: '{}' {:.1}% of DB time.",
+                        sev.emoji(), sev.label(), event, pct
+                    );
+                    alerts.push(Alert::new(10, sev, event, Some(pct), Some(t.wait_pct.for_severity(sev)), text));
                 }
             }
         }
 
         // [RULE 11] buffer busy waits
         if row.contains("buffer busy waits") {
-            alerts.push("🟠 buffer busy waits — hot blocks likely.".into());
+            alerts.push(Alert::new(
+                11,
+                Severity::Warning,
+                "buffer busy waits",
+                None,
+                None,
+                "🟠 buffer busy waits — hot blocks likely.".into(),
+            ));
         }
 
         // [RULE 13] row lock contention
         if row.contains("row lock contention") {
             if let Some(pct) = extract_percent_from_wait_row(row) {
-                if pct > t.row_lock_pct {
-                    alerts.push(format!("🔴 Row lock contention: {:.1}% — investigate blocking.", pct));
+                if let Some(sev) = t.row_lock_pct.check(pct) {
+                    let text = format!(
+                        "{} {}: Row lock contention: {:.1}% — investigate blocking.",
+                        sev.emoji(), sev.label(), pct
+                    );
+                    alerts.push(Alert::new(
+                        13,
+                        sev,
+                        "row lock contention",
+                        Some(pct),
+                        Some(t.row_lock_pct.for_severity(sev)),
+                        text,
+                    ));
                 }
             }
         }
@@ -382,27 +531,39 @@ fn alert_on_fg_waits(table: &[String], t: &AlertThresholds) -> Vec<String> {
         // [RULE 14] GC remote, any "gc" event
         if row.to_lowercase().contains("gc") {
             if let Some(pct) = extract_percent_from_wait_row(row) {
-                if pct > t.gc_remote_pct {
-                    alerts.push(format!(
-                        "🔴 Global Cache (RAC) event '{}': {:.1}% — possible RAC/interconnect issue.",
-                        extract_event_name(row), pct
-                    ));
+                if let Some(sev) = t.gc_remote_pct.check(pct) {
+                    let event = extract_event_name(row);
+                    let text = format!(
+                        "{} {}: Global Cache (RAC) event '{}': {:.1}% — possible RAC/interconnect issue.",
+                        sev.emoji(), sev.label(), event, pct
+                    );
+                    alerts.push(Alert::new(14, sev, event, Some(pct), Some(t.gc_remote_pct.for_severity(sev)), text));
                 }
             }
         }
 
         // [RULE 15] Enqueue/contention (enq:)
         if row.contains("enq:") {
-            alerts.push(format!("🟠 Contention: '{}' seen. Check blocking/locking.", extract_event_name(row)));
+            let event = extract_event_name(row);
+            let text = format!("🟠 Contention: '{}' seen. Check blocking/locking.", event);
+            alerts.push(Alert::new(15, Severity::Warning, event, None, None, text));
         }
 
         // [RULE 9] db file parallel read
         if row.contains("db file parallel read") {
             if let Some(pct) = extract_percent_from_wait_row(row) {
-                if pct > t.wait_pct {
-                    alerts.push(format!(
-                        "🟠 High 'db file parallel read': {:.1}% — possible parallel I/O tuning needed.",
-                        pct
+                if let Some(sev) = t.wait_pct.check(pct) {
+                    let text = format!(
+                        "{} {}: High 'db file parallel read': {:.1}% — possible parallel I/O tuning needed.",
+                        sev.emoji(), sev.label(), pct
+                    );
+                    alerts.push(Alert::new(
+                        9,
+                        sev,
+                        "db file parallel read",
+                        Some(pct),
+                        Some(t.wait_pct.for_severity(sev)),
+                        text,
                     ));
                 }
             }
@@ -410,7 +571,14 @@ fn alert_on_fg_waits(table: &[String], t: &AlertThresholds) -> Vec<String> {
 
         // [RULE 12] Temp I/O (direct path write temp)
         if row.contains("direct path write temp") || row.contains("direct path read temp") {
-            alerts.push("🟡 Temp I/O — heavy temp usage detected.".into());
+            alerts.push(Alert::new(
+                12,
+                Severity::Warning,
+                "temp i/o",
+                None,
+                None,
+                "🟡 Temp I/O — heavy temp usage detected.".into(),
+            ));
         }
     }
     alerts
@@ -419,19 +587,37 @@ fn alert_on_fg_waits(table: &[String], t: &AlertThresholds) -> Vec<String> {
 /* ========================================================================
    NEW: Background Wait Events Section (add parsing if your reports have it)
    ======================================================================== */
-fn alert_on_bg_waits(table: &[String], t: &AlertThresholds) -> Vec<String> {
+fn alert_on_bg_waits(table: &[String], t: &AlertThresholds) -> Vec<Alert> {
     let mut alerts = Vec::new();
 
     for row in table {
         if row.contains("log file parallel write") {
             if let Some(pct) = extract_percent_from_wait_row(row) {
-                if pct > t.wait_pct {
-                    alerts.push("🟡 High background 'log file parallel write' — possible LGWR or storage bottleneck.".into());
+                if let Some(sev) = t.wait_pct.check(pct) {
+                    let text = format!(
+                        "{} {}: High background 'log file parallel write' — possible LGWR or storage bottleneck.",
+                        sev.emoji(), sev.label()
+                    );
+                    alerts.push(Alert::new(
+                        23,
+                        sev,
+                        "log file parallel write",
+                        Some(pct),
+                        Some(t.wait_pct.for_severity(sev)),
+                        text,
+                    ));
                 }
             }
         }
         if row.contains("db file parallel write") {
-            alerts.push("🟠 Background 'db file parallel write' seen — possible checkpoint/backup or async I/O pressure.".into());
+            alerts.push(Alert::new(
+                24,
+                Severity::Warning,
+                "db file parallel write",
+                None,
+                None,
+                "🟠 Background 'db file parallel write' seen — possible checkpoint/backup or async I/O pressure.".into(),
+            ));
         }
         // ...add other background events here...
     }
@@ -441,28 +627,41 @@ fn alert_on_bg_waits(table: &[String], t: &AlertThresholds) -> Vec<String> {
 /* ========================================================================
    Wait Class Table Rules — mostly unchanged, but now flags more classes
    ======================================================================== */
-fn alert_on_wait_classes(table: &[String], t: &AlertThresholds) -> Vec<String> {
+fn alert_on_wait_classes(table: &[String], t: &AlertThresholds) -> Vec<Alert> {
     let mut alerts = Vec::new();
 
     for row in table {
         if row.contains("User I/O") {
             if let Some(pct) = extract_percent_from_wait_row(row) {
-                if pct > t.wait_pct {
-                    alerts.push("🟡 High User I/O class — DB is I/O-bound.".into());
+                if let Some(sev) = t.wait_pct.check(pct) {
+                    let text = format!("{} {}: High User I/O class — DB is I/O-bound.", sev.emoji(), sev.label());
+                    alerts.push(Alert::new(20, sev, "User I/O", Some(pct), Some(t.wait_pct.for_severity(sev)), text));
                 }
             }
         }
         if row.contains("Commit") {
             if let Some(pct) = extract_percent_from_wait_row(row) {
-                if pct > t.wait_pct {
-                    alerts.push("🟠 Commit wait class elevated — redo pressure.".into());
+                if let Some(sev) = t.wait_pct.check(pct) {
+                    let text = format!("{} {}: Commit wait class elevated — redo pressure.", sev.emoji(), sev.label());
+                    alerts.push(Alert::new(21, sev, "Commit", Some(pct), Some(t.wait_pct.for_severity(sev)), text));
                 }
             }
         }
         if row.contains("Concurrency") {
             if let Some(pct) = extract_percent_from_wait_row(row) {
-                if pct > t.row_lock_pct {
-                    alerts.push("🔴 High concurrency wait class — locking/contention suspected.".into());
+                if let Some(sev) = t.row_lock_pct.check(pct) {
+                    let text = format!(
+                        "{} {}: High concurrency wait class — locking/contention suspected.",
+                        sev.emoji(), sev.label()
+                    );
+                    alerts.push(Alert::new(
+                        22,
+                        sev,
+                        "Concurrency",
+                        Some(pct),
+                        Some(t.row_lock_pct.for_severity(sev)),
+                        text,
+                    ));
                 }
             }
         }
@@ -474,7 +673,7 @@ fn alert_on_wait_classes(table: &[String], t: &AlertThresholds) -> Vec<String> {
 /* ========================================================================
    IO Profile: Add ratio logic, scattered vs sequential, and more
    ======================================================================== */
-fn alert_on_io_profile(table: &[String], t: &AlertThresholds) -> Vec<String> {
+fn alert_on_io_profile(table: &[String], t: &AlertThresholds) -> Vec<Alert> {
     let mut alerts = Vec::new();
     let num_re = Regex::new(r"(\d[\d,\.]+)").unwrap();
 
@@ -494,8 +693,19 @@ fn alert_on_io_profile(table: &[String], t: &AlertThresholds) -> Vec<String> {
                 .collect();
             if let Some(first) = vals.first() {
                 total_requests = Some(*first);
-                if *first > t.io_request_rate {
-                    alerts.push(format!("🟠 High I/O request rate: {} (> {} threshold).", first, t.io_request_rate));
+                if let Some(sev) = t.io_request_rate.check(*first) {
+                    let text = format!(
+                        "{} {}: High I/O request rate: {} (> {} threshold).",
+                        sev.emoji(), sev.label(), first, t.io_request_rate.for_severity(sev)
+                    );
+                    alerts.push(Alert::new(
+                        1,
+                        sev,
+                        "io_request_rate",
+                        Some(*first),
+                        Some(t.io_request_rate.for_severity(sev)),
+                        text,
+                    ));
                 }
             }
         }
@@ -536,113 +746,143 @@ fn alert_on_io_profile(table: &[String], t: &AlertThresholds) -> Vec<String> {
     // [RULE 8] Write/Read ratio
     if let (Some(w), Some(r)) = (write_reqs, read_reqs) {
         if w > r * 2.0 {
-            alerts.push(format!(
+            let text = format!(
                 "🟠 Write requests are more than 2x reads ({:.2} writes/sec vs {:.2} reads/sec). Check for redo/temp bottleneck.",
                 w, r
-            ));
+            );
+            alerts.push(Alert::new(8, Severity::Warning, "write_read_ratio", Some(w), Some(r * 2.0), text));
         }
     }
     // [RULE 17] Throughput anomaly
     if let (Some(total), Some(rmb), Some(wmb)) = (total_requests, read_mb, write_mb) {
         let total_mb = rmb + wmb;
         if total > 0.0 && total_mb < 1.0 {
-            alerts.push(format!(
+            let text = format!(
                 "🟡 High IOPS ({:.1}) but very low MB/sec ({:.2}). Many small I/Os? Check block size or inefficient access.",
                 total, total_mb
-            ));
+            );
+            alerts.push(Alert::new(17, Severity::Warning, "throughput_anomaly", Some(total_mb), Some(1.0), text));
         }
     }
     // [RULE 19] Scattered vs sequential
     if let (Some(sc), Some(seq)) = (scattered_reads, sequential_reads) {
         if sc > seq * 2.0 {
-            alerts.push(format!(
+            let text = format!(
                 "🟠 'db file scattered read' >2x 'sequential read' ({:.2} vs {:.2}). Full table scans may be dominating.",
                 sc, seq
-            ));
+            );
+            alerts.push(Alert::new(19, Severity::Warning, "scattered_vs_sequential", Some(sc), Some(seq * 2.0), text));
         }
     }
     alerts
 }
 
 /* ========================================================================
-   OUTPUT — Add new section for Background Waits if needed
+   Tablespace IO Stats: per-tablespace latency, using per-object overrides
    ======================================================================== */
-fn print_table_with_alert<F>(
-    lines: &[String],
-    title_pat: &str,
-    section_name: &str,
-    alert_fn: F,
-    thresholds: &AlertThresholds,
-)
-where
-    F: Fn(&[String], &AlertThresholds) -> Vec<String>,
-{
-    println!("## {}\n", section_name);
 
-    if let Some(table) = extract_native_table(lines, title_pat, 2) {
-        for l in &table {
-            println!("{}", l);
+/// [RULE 28] Average read latency per tablespace, the one extracted table
+/// that actually carries an object name `AlertThresholds::for_object` can
+/// key its `[tablespace.NAME]` overrides on. AWR prints the tablespace name
+/// on its own line, then a numbers-only row below it for that tablespace's
+/// Reads / Reads/s / **Av Rd(ms)** / Av Blks/Rd / Writes / ...; this reads
+/// that "name line, then numbers line" pairing the same way the rest of
+/// this file's extraction does (grab the first/Nth numeric token), rather
+/// than a full column-aligned table parser.
+fn alert_on_tablespace_io(table: &[String], t: &AlertThresholds) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+    let num_re = Regex::new(r"[\d,]*\.?\d+").unwrap();
+    let mut current: Option<String> = None;
+
+    for row in table {
+        let trim = row.trim();
+        if trim.is_empty() || trim.chars().all(|c| c == '-') {
+            continue;
         }
 
-        let alerts = alert_fn(&table, thresholds);
+        let nums: Vec<f64> =
+            num_re.find_iter(trim).filter_map(|m| m.as_str().replace(',', "").parse().ok()).collect();
 
-        if alerts.is_empty() {
-            println!("\nNo immediate I/O issues flagged.\n");
-        } else {
-            println!("\n### 🚩 Analysis / Comments");
-            for a in alerts {
-                println!("- {}", a);
+        if nums.is_empty() {
+            current = Some(trim.to_string());
+            continue;
+        }
+
+        // Columns: Reads, Reads/s, Av Rd(ms), Av Blks/Rd, Writes, ...
+        if let (Some(name), Some(&rd_ms)) = (&current, nums.get(2)) {
+            let object_thresholds = t.for_object(name);
+            if let Some(sev) = object_thresholds.io_latency_ms.check(rd_ms) {
+                let text = format!(
+                    "{} {}: Tablespace '{}' average read latency {:.2}ms (> {:.1}ms threshold).",
+                    sev.emoji(),
+                    sev.label(),
+                    name,
+                    rd_ms,
+                    object_thresholds.io_latency_ms.for_severity(sev)
+                );
+                alerts.push(Alert::new(
+                    28,
+                    sev,
+                    name.clone(),
+                    Some(rd_ms),
+                    Some(object_thresholds.io_latency_ms.for_severity(sev)),
+                    text,
+                ));
             }
-            println!();
         }
-    } else {
-        println!("*No {} section found.*\n", section_name.to_lowercase());
     }
+    alerts
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        usage();
+/* ========================================================================
+   OUTPUT — one Section per extracted table, rendered as text or JSON
+   ======================================================================== */
+
+/// Extracts one table and runs its alert function, independent of how the
+/// result ends up rendered (`render_text` / `render_json`).
+fn build_section<F>(lines: &[String], title_pat: &str, section_name: &str, alert_fn: F, thresholds: &AlertThresholds) -> Section
+where
+    F: Fn(&[String], &AlertThresholds) -> Vec<Alert>,
+{
+    match extract_native_table(lines, title_pat, 2) {
+        Some(table) => {
+            let alerts = alert_fn(&table, thresholds);
+            Section { name: section_name.to_string(), found: true, rows: table, alerts }
+        }
+        None => Section { name: section_name.to_string(), found: false, rows: Vec::new(), alerts: Vec::new() },
     }
-    println!("awr_io_analyze - Version {}",VERSION_NUMBER);
-    let filename = &args[1];
-    let config_path = if args.len() >= 3 { &args[2] } else { "awr_io_analyze.toml" };
-    let thresholds = load_thresholds_from_file(config_path);
-    let lines = read_lines(filename);
+}
 
+fn render_text(filename: &str, thresholds: &AlertThresholds, sections: &[Section]) {
+    println!("awr_io_analyze - Version {}", VERSION_NUMBER);
     println!("# AWR I/O Analysis for `{}`\n", filename);
     println!("**Thresholds: {:?}**\n", thresholds);
 
-    print_table_with_alert(
-        &lines,
-        r"Top 10 Foreground Events by Total Wait Time",
-        "Foreground Wait Events",
-        alert_on_fg_waits,
-        &thresholds,
-    );
-    // Optionally add Background Waits if your AWR has such a section:
-    // print_table_with_alert(
-    //     &lines,
-    //     r"Top 10 Background Events by Total Wait Time",
-    //     "Background Wait Events",
-    //     alert_on_bg_waits,
-    //     &thresholds,
-    // );
-    print_table_with_alert(
-        &lines,
-        r"Wait Classes by Total Wait Time",
-        "Wait Classes",
-        alert_on_wait_classes,
-        &thresholds,
-    );
-    print_table_with_alert(
-        &lines,
-        r"IO Profile",
-        "IO Profile",
-        alert_on_io_profile,
-        &thresholds,
-    );
+    for section in sections {
+        println!("## {}\n", section.name);
+
+        if !section.found {
+            println!("*No {} section found.*\n", section.name.to_lowercase());
+            continue;
+        }
+
+        for l in &section.rows {
+            println!("{}", l);
+        }
+
+        if section.alerts.is_empty() {
+            println!("\nNo immediate I/O issues flagged.\n");
+        } else {
+            println!("\n### 🚩 Analysis / Comments");
+            for a in &section.alerts {
+                println!("- {}", a.text);
+            }
+            println!();
+        }
+    }
+
+    let health_score = health::score(sections, thresholds);
+    print!("{}", health::render_summary(&health_score));
 
     println!("## Knowledge Base / Best Practices");
     println!("- log file sync / parallel write: redo bottleneck.");
@@ -655,3 +895,291 @@ fn main() {
     println!("- Always correlate waits with SQL + I/O subsystem.\n");
 }
 
+#[derive(serde::Serialize)]
+struct JsonReport<'a> {
+    report_file: &'a str,
+    thresholds: &'a AlertThresholds,
+    sections: &'a [Section],
+    health: health::HealthScore,
+}
+
+fn render_json(filename: &str, thresholds: &AlertThresholds, sections: &[Section]) {
+    let health = health::score(sections, thresholds);
+    let report = JsonReport { report_file: filename, thresholds, sections, health };
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("error: failed to serialize report to JSON: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    match args.get(1).map(String::as_str) {
+        Some("analyze") => cmd_analyze(&args[2..]),
+        Some("diff") => cmd_diff(&args[2..]),
+        Some("export") => cmd_export(&args[2..]),
+        Some("thresholds") => cmd_thresholds(&args[2..]),
+        Some("-h") | Some("--help") => usage(),
+        Some(other) => {
+            eprintln!("error: unknown command '{}'\n", other);
+            usage();
+        }
+        None => usage(),
+    }
+}
+
+/// Parses a shared `--format text|json` flag, exiting with an error on
+/// anything else.
+fn parse_output_format(value: Option<&String>) -> OutputFormat {
+    match value.map(String::as_str) {
+        Some("json") => OutputFormat::Json,
+        Some("text") => OutputFormat::Text,
+        other => {
+            eprintln!("error: --format expects 'text' or 'json', got {:?}", other);
+            process::exit(1);
+        }
+    }
+}
+
+fn cmd_analyze(args: &[String]) {
+    let mut format = OutputFormat::Text;
+    let mut config_path = "awr_io_analyze.toml".to_string();
+    let mut positional = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                i += 1;
+                format = parse_output_format(args.get(i));
+            }
+            "--config" => {
+                i += 1;
+                config_path = args.get(i).cloned().unwrap_or_else(|| {
+                    eprintln!("error: --config expects a path");
+                    process::exit(1);
+                });
+            }
+            "-h" | "--help" => usage_analyze(),
+            arg => positional.push(arg.to_string()),
+        }
+        i += 1;
+    }
+
+    if positional.is_empty() {
+        usage_analyze();
+    }
+
+    let filename = &positional[0];
+    let thresholds = ThresholdSource::new(&config_path).load();
+    let lines = read_lines(filename);
+
+    let sections = vec![
+        build_section(&lines, r"Top 10 Foreground Events by Total Wait Time", "Foreground Wait Events", alert_on_fg_waits, &thresholds),
+        build_section(&lines, r"Top 10 Background Events by Total Wait Time", "Background Wait Events", alert_on_bg_waits, &thresholds),
+        build_section(&lines, r"Wait Classes by Total Wait Time", "Wait Classes", alert_on_wait_classes, &thresholds),
+        build_section(&lines, r"IO Profile", "IO Profile", alert_on_io_profile, &thresholds),
+        build_section(&lines, r"Tablespace IO Stats", "Tablespace IO Stats", alert_on_tablespace_io, &thresholds),
+    ];
+
+    match format {
+        OutputFormat::Text => render_text(filename, &thresholds, &sections),
+        OutputFormat::Json => render_json(filename, &thresholds, &sections),
+    }
+}
+
+fn cmd_diff(args: &[String]) {
+    let mut format = OutputFormat::Text;
+    let mut config_path = "awr_io_analyze.toml".to_string();
+    let mut positional = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                i += 1;
+                format = parse_output_format(args.get(i));
+            }
+            "--config" => {
+                i += 1;
+                config_path = args.get(i).cloned().unwrap_or_else(|| {
+                    eprintln!("error: --config expects a path");
+                    process::exit(1);
+                });
+            }
+            "-h" | "--help" => usage_diff(),
+            arg => positional.push(arg.to_string()),
+        }
+        i += 1;
+    }
+
+    if positional.len() < 2 {
+        usage_diff();
+    }
+
+    // Reload the config once per snapshot file read (rather than once up
+    // front). All files are read before the analysis runs, and
+    // `trend::analyze` gets the single set loaded after the last read:
+    // thresholds do not vary from one snapshot to the next.
+    let source = ThresholdSource::new(&config_path);
+    let snapshots: Vec<(String, Vec<String>)> = positional
+        .iter()
+        .map(|path| {
+            source.reload_if_changed();
+            (path.clone(), read_lines(path))
+        })
+        .collect();
+    let thresholds = source.load();
+    let label = format!("{} → {}", snapshots[0].0, snapshots[snapshots.len() - 1].0);
+    let sections = vec![trend::analyze(&snapshots, &thresholds)];
+
+    match format {
+        OutputFormat::Text => render_text(&label, &thresholds, &sections),
+        OutputFormat::Json => render_json(&label, &thresholds, &sections),
+    }
+}
+
+enum ExportFormat {
+    Json,
+    Prometheus,
+}
+
+fn cmd_export(args: &[String]) {
+    let mut export_format: Option<ExportFormat> = None;
+    let mut config_path = "awr_io_analyze.toml".to_string();
+    let mut outfile: Option<String> = None;
+    let mut positional = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                i += 1;
+                export_format = match args.get(i).map(String::as_str) {
+                    Some("json") => Some(ExportFormat::Json),
+                    Some("prometheus") => Some(ExportFormat::Prometheus),
+                    other => {
+                        eprintln!("error: --format expects 'json' or 'prometheus', got {:?}", other);
+                        process::exit(1);
+                    }
+                };
+            }
+            "--out" => {
+                i += 1;
+                outfile = Some(args.get(i).cloned().unwrap_or_else(|| {
+                    eprintln!("error: --out expects a path");
+                    process::exit(1);
+                }));
+            }
+            "--config" => {
+                i += 1;
+                config_path = args.get(i).cloned().unwrap_or_else(|| {
+                    eprintln!("error: --config expects a path");
+                    process::exit(1);
+                });
+            }
+            "-h" | "--help" => usage_export(),
+            arg => positional.push(arg.to_string()),
+        }
+        i += 1;
+    }
+
+    if positional.is_empty() {
+        usage_export();
+    }
+    let export_format = export_format.unwrap_or_else(|| {
+        eprintln!("error: export requires --format json|prometheus");
+        process::exit(1);
+    });
+    let outfile = outfile.unwrap_or_else(|| {
+        eprintln!("error: export requires --out <outfile>");
+        process::exit(1);
+    });
+
+    let filename = &positional[0];
+    let thresholds = ThresholdSource::new(&config_path).load();
+    let lines = read_lines(filename);
+
+    let (text, kind) = match export_format {
+        ExportFormat::Prometheus => (prometheus::render(&lines, &thresholds), "Prometheus"),
+        ExportFormat::Json => {
+            let sections = vec![
+                build_section(&lines, r"Top 10 Foreground Events by Total Wait Time", "Foreground Wait Events", alert_on_fg_waits, &thresholds),
+                build_section(&lines, r"Top 10 Background Events by Total Wait Time", "Background Wait Events", alert_on_bg_waits, &thresholds),
+                build_section(&lines, r"Wait Classes by Total Wait Time", "Wait Classes", alert_on_wait_classes, &thresholds),
+                build_section(&lines, r"IO Profile", "IO Profile", alert_on_io_profile, &thresholds),
+                build_section(&lines, r"Tablespace IO Stats", "Tablespace IO Stats", alert_on_tablespace_io, &thresholds),
+            ];
+            let health = health::score(&sections, &thresholds);
+            let report = JsonReport { report_file: filename, thresholds: &thresholds, sections: &sections, health };
+            let json = serde_json::to_string_pretty(&report).unwrap_or_else(|e| {
+                eprintln!("error: failed to serialize report to JSON: {}", e);
+                process::exit(1);
+            });
+            (json, "JSON")
+        }
+    };
+
+    if let Err(e) = fs::write(&outfile, text) {
+        eprintln!("error: failed to write '{}': {}", outfile, e);
+        process::exit(1);
+    }
+    println!("Wrote {} to {}", kind, outfile);
+}
+
+fn cmd_thresholds(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("show") => cmd_thresholds_show(&args[1..]),
+        Some("init") => cmd_thresholds_init(&args[1..]),
+        _ => usage_thresholds(),
+    }
+}
+
+fn cmd_thresholds_show(args: &[String]) {
+    let mut config_path = "awr_io_analyze.toml".to_string();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--config" => {
+                i += 1;
+                config_path = args.get(i).cloned().unwrap_or_else(|| {
+                    eprintln!("error: --config expects a path");
+                    process::exit(1);
+                });
+            }
+            "-h" | "--help" => usage_thresholds(),
+            other => {
+                eprintln!("error: unexpected argument '{}'", other);
+                usage_thresholds();
+            }
+        }
+        i += 1;
+    }
+
+    let thresholds = load_thresholds_from_file(&config_path);
+    match serde_json::to_string_pretty(&thresholds) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("error: failed to serialize thresholds to JSON: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn cmd_thresholds_init(args: &[String]) {
+    if args.first().map(String::as_str) == Some("-h") || args.first().map(String::as_str) == Some("--help") {
+        usage_thresholds();
+    }
+    let path = args.first().cloned().unwrap_or_else(|| "awr_io_analyze.toml".to_string());
+    if let Err(e) = fs::write(&path, thresholds::default_config_toml()) {
+        eprintln!("error: failed to write '{}': {}", path, e);
+        process::exit(1);
+    }
+    println!("Wrote default thresholds to {}", path);
+}
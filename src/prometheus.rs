@@ -0,0 +1,143 @@
+/*!
+ * prometheus.rs — Prometheus exposition format export
+ *
+ * `export --format prometheus --out <outfile>` converts the same numeric values the other
+ * output modes already extract (IO Profile rates/throughput, per-event
+ * wait-class %, per-event avg latency) into Prometheus text exposition
+ * format: one `# HELP`/`# TYPE` block per metric, with `{event="..."}` /
+ * `{class="..."}` labels built from `extract_event_name` so label values
+ * match the event keys a `--format json` or `diff` consumer would see.
+ *
+ * Per-event latencies are also rolled into a histogram with exponential
+ * bucket boundaries (`base * factor^i` for `count` buckets), so a scrape
+ * target can track the latency distribution across snapshots over time
+ * rather than just a single gauge per event.
+ *
+ * Co-developed by Laurence Oberman and ChatGPT (OpenAI), 2025.
+ * License: GPLv3+
+ */
+
+use std::collections::HashSet;
+
+use regex::Regex;
+
+use crate::thresholds::AlertThresholds;
+use crate::{extract_event_name, extract_latency_ms, extract_native_table, extract_percent_from_wait_row};
+
+/// First numeric value on a line mentioning `needle`, from an already
+/// extracted IO Profile table.
+fn io_profile_value(table: &[String], needle: &str) -> Option<f64> {
+    let num_re = Regex::new(r"(\d[\d,\.]+)").unwrap();
+    table
+        .iter()
+        .find(|l| l.contains(needle))
+        .and_then(|l| num_re.captures(l).and_then(|c| c[1].replace(',', "").parse().ok()))
+}
+
+/// Appends one sample line for `name{labels} value`, emitting the `# HELP`/
+/// `# TYPE` header only the first time `name` is seen — a report with N
+/// foreground events means N calls per metric name, and a duplicate
+/// HELP/TYPE block for the same metric is invalid exposition format
+/// (Prometheus rejects the scrape with "second HELP line for metric name").
+fn gauge(out: &mut String, seen: &mut HashSet<&'static str>, name: &'static str, help: &str, value: f64, labels: &str) {
+    if seen.insert(name) {
+        out.push_str(&format!("# HELP {} {}\n", name, help));
+        out.push_str(&format!("# TYPE {} gauge\n", name));
+    }
+    out.push_str(&format!("{}{} {}\n", name, labels, value));
+}
+
+/// Exponential bucket boundaries: `base`, `base*factor`, `base*factor^2`, ...
+fn histogram_buckets(base: f64, factor: f64, count: u32) -> Vec<f64> {
+    (0..count).map(|i| base * factor.powi(i as i32)).collect()
+}
+
+fn latency_histogram(out: &mut String, values: &[f64], t: &AlertThresholds) {
+    let name = "awr_event_latency_ms";
+    let buckets = histogram_buckets(t.histogram_bucket_base_ms, t.histogram_bucket_factor, t.histogram_bucket_count);
+
+    out.push_str(&format!(
+        "# HELP {}_histogram Distribution of per-event average wait latency (ms) across foreground events in this snapshot.\n",
+        name
+    ));
+    out.push_str(&format!("# TYPE {}_histogram histogram\n", name));
+    for b in &buckets {
+        let c = values.iter().filter(|v| **v <= *b).count();
+        out.push_str(&format!("{}_histogram_bucket{{le=\"{}\"}} {}\n", name, b, c));
+    }
+    out.push_str(&format!("{}_histogram_bucket{{le=\"+Inf\"}} {}\n", name, values.len()));
+    out.push_str(&format!("{}_histogram_sum {}\n", name, values.iter().sum::<f64>()));
+    out.push_str(&format!("{}_histogram_count {}\n", name, values.len()));
+}
+
+/// Renders the Foreground Wait Events, Wait Classes, and IO Profile tables
+/// from one AWR report as Prometheus exposition text.
+pub fn render(lines: &[String], t: &AlertThresholds) -> String {
+    let mut out = String::new();
+    let mut seen = HashSet::new();
+    let mut latencies = Vec::new();
+
+    if let Some(table) = extract_native_table(lines, r"Top 10 Foreground Events by Total Wait Time", 2) {
+        for row in &table {
+            let event = extract_event_name(row);
+            if let Some(pct) = extract_percent_from_wait_row(row) {
+                gauge(
+                    &mut out,
+                    &mut seen,
+                    "awr_event_wait_pct",
+                    "Percent of DB time spent in this foreground wait event.",
+                    pct,
+                    &format!("{{event=\"{}\"}}", event),
+                );
+            }
+            if let Some(lat) = extract_latency_ms(row) {
+                gauge(
+                    &mut out,
+                    &mut seen,
+                    "awr_event_latency_ms",
+                    "Average wait latency (ms) for this foreground wait event.",
+                    lat,
+                    &format!("{{event=\"{}\"}}", event),
+                );
+                latencies.push(lat);
+            }
+        }
+    }
+
+    if let Some(table) = extract_native_table(lines, r"Wait Classes by Total Wait Time", 2) {
+        for row in &table {
+            if let Some(pct) = extract_percent_from_wait_row(row) {
+                let class = extract_event_name(row);
+                gauge(
+                    &mut out,
+                    &mut seen,
+                    "awr_wait_class_pct",
+                    "Percent of DB time spent in this wait class.",
+                    pct,
+                    &format!("{{class=\"{}\"}}", class),
+                );
+            }
+        }
+    }
+
+    if let Some(table) = extract_native_table(lines, r"IO Profile", 2) {
+        let metrics: &[(&'static str, &str, &str)] = &[
+            ("awr_io_request_rate", "Total I/O requests per second.", "Total Requests:"),
+            ("awr_io_read_requests_per_sec", "Read requests per second.", "Read Requests per Second"),
+            ("awr_io_write_requests_per_sec", "Write requests per second.", "Write Requests per Second"),
+            ("awr_io_read_mb_per_sec", "Read throughput in MB/sec.", "Read MB/sec"),
+            ("awr_io_write_mb_per_sec", "Write throughput in MB/sec.", "Write MB/sec"),
+        ];
+        for (name, help, needle) in metrics {
+            if let Some(v) = io_profile_value(&table, needle) {
+                gauge(&mut out, &mut seen, name, help, v, "");
+            }
+        }
+    }
+
+    if !latencies.is_empty() {
+        latency_histogram(&mut out, &latencies, t);
+    }
+
+    out
+}
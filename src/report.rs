@@ -0,0 +1,77 @@
+/*!
+ * report.rs — Typed alert/report model shared by the text and JSON renderers
+ *
+ * The alert functions (`alert_on_fg_waits` et al.) used to build
+ * pre-formatted `Vec<String>` lines for stdout. They now build `Alert`
+ * values instead: the structured fields a JSON consumer needs (rule id,
+ * severity, event, measured value, threshold), plus the exact text line
+ * the markdown renderer prints, so `--format text` and `--format json`
+ * are built from the same alerts.
+ *
+ * Co-developed by Laurence Oberman and ChatGPT (OpenAI), 2025.
+ * License: GPLv3+
+ */
+
+use serde::Serialize;
+
+use crate::thresholds::Severity;
+
+/// Machine-readable severity for JSON consumers, derived from the
+/// 🔵/🟡/🟠/🔴 emoji already used in rendered text: 🔵 → info, 🟡/🟠 → warn,
+/// 🔴 → critical.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    Info,
+    Warn,
+    Critical,
+}
+
+impl From<Severity> for AlertSeverity {
+    fn from(sev: Severity) -> Self {
+        match sev {
+            Severity::Info => AlertSeverity::Info,
+            Severity::Warning => AlertSeverity::Warn,
+            Severity::Critical => AlertSeverity::Critical,
+        }
+    }
+}
+
+/// One flagged condition from an alert function.
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub rule_id: u32,
+    pub severity: AlertSeverity,
+    pub event: String,
+    pub value: Option<f64>,
+    pub threshold: Option<f64>,
+    /// The exact line the text/markdown renderer prints under "### 🚩
+    /// Analysis / Comments". Not part of the JSON alert object — a JSON
+    /// consumer gets the structured fields above instead.
+    #[serde(skip)]
+    pub text: String,
+}
+
+impl Alert {
+    pub fn new(
+        rule_id: u32,
+        severity: impl Into<AlertSeverity>,
+        event: impl Into<String>,
+        value: Option<f64>,
+        threshold: Option<f64>,
+        text: String,
+    ) -> Self {
+        Alert { rule_id, severity: severity.into(), event: event.into(), value, threshold, text }
+    }
+}
+
+/// One extracted AWR table (e.g. Foreground Wait Events, IO Profile) or the
+/// `diff` trend section, with its raw rows and the alerts flagged against it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Section {
+    pub name: String,
+    /// Whether the section's table was found at all in the report.
+    pub found: bool,
+    pub rows: Vec<String>,
+    pub alerts: Vec<Alert>,
+}
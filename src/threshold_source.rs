@@ -0,0 +1,74 @@
+/*!
+ * threshold_source.rs — Runtime-reconfigurable threshold source
+ *
+ * Wraps `AlertThresholds` so a long-running/streaming analysis can pick up
+ * edits to the config file without restarting. Analysis code reads the
+ * current snapshot via a cheap `load()` per evaluation; a caller-driven
+ * `reload_if_changed()` (e.g. once per incoming AWR export) re-parses the
+ * file only when its mtime has actually moved, and keeps serving the
+ * previous good values — with a warning — if the new file fails to parse.
+ *
+ * Co-developed by Laurence Oberman and ChatGPT (OpenAI), 2025.
+ * License: GPLv3+
+ */
+
+use std::fs;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use crate::thresholds::{load_thresholds_from_file, try_load_thresholds_from_file, AlertThresholds, LoadError};
+
+fn file_mtime(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Hot-reloadable handle around a config-file-backed `AlertThresholds`.
+pub struct ThresholdSource {
+    path: String,
+    current: RwLock<Arc<AlertThresholds>>,
+    last_mtime: Mutex<Option<SystemTime>>,
+}
+
+impl ThresholdSource {
+    pub fn new(path: &str) -> Self {
+        ThresholdSource {
+            path: path.to_string(),
+            current: RwLock::new(Arc::new(load_thresholds_from_file(path))),
+            last_mtime: Mutex::new(file_mtime(path)),
+        }
+    }
+
+    /// Cheap read of the currently active thresholds, for per-evaluation use.
+    pub fn load(&self) -> Arc<AlertThresholds> {
+        Arc::clone(&self.current.read().unwrap())
+    }
+
+    /// Re-parses the config file and atomically swaps it in if its mtime has
+    /// changed since the last call. Safe to call once per snapshot in a
+    /// continuous analysis loop — a no-op when the file hasn't changed.
+    /// Keeps the previous good thresholds (with a warning) if the file has
+    /// become unreadable or fails to parse.
+    pub fn reload_if_changed(&self) {
+        let mtime = file_mtime(&self.path);
+        {
+            let mut last = self.last_mtime.lock().unwrap();
+            if mtime == *last {
+                return;
+            }
+            *last = mtime;
+        }
+
+        match try_load_thresholds_from_file(&self.path) {
+            Ok(fresh) => *self.current.write().unwrap() = Arc::new(fresh),
+            Err(LoadError::Unreadable) => {
+                eprintln!("warning: '{}' became unreadable, keeping previous thresholds", self.path);
+            }
+            Err(LoadError::Parse(e)) => {
+                eprintln!(
+                    "warning: failed to parse '{}', keeping previous thresholds: {}",
+                    self.path, e
+                );
+            }
+        }
+    }
+}
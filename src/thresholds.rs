@@ -8,66 +8,719 @@
  * License: GPLv3+
  */
 
+use std::collections::HashMap;
 use std::fs;
 
+use serde::{Deserialize, Serialize};
+
+/// Severity of a breached threshold, from least to most urgent.
+///
+/// Ordered so `Severity::Critical > Severity::Warning > Severity::Info`,
+/// which lets report consumers sort/filter alerts by how bad they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    /// Emoji used in rendered alert text, matching the existing 🔵/🟡/🔴 convention.
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Severity::Info => "🔵",
+            Severity::Warning => "🟡",
+            Severity::Critical => "🔴",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "INFO",
+            Severity::Warning => "WARNING",
+            Severity::Critical => "CRITICAL",
+        }
+    }
+}
+
+/// A three-level severity band for a single upper-bound metric.
+///
+/// Replaces the old flat "over limit or not" scalar: a value can now be
+/// graded INFO / WARNING / CRITICAL instead of just alerting-or-not.
+/// Invariant: `critical >= warning >= info`, enforced by `new()` and
+/// `apply_override()` clamping any band upward to its lower neighbor —
+/// load-bearing because `RawThresholds`/`ThresholdOverride` default each
+/// `_info`/`_warning`/`_critical` field independently, so a config setting
+/// only `io_latency_ms_warning` must not leave the unset `critical` below it.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Tier {
+    pub info: f64,
+    pub warning: f64,
+    pub critical: f64,
+}
+
+impl Tier {
+    pub fn new(info: f64, warning: f64, critical: f64) -> Self {
+        Tier { info, warning, critical }.normalized()
+    }
+
+    /// Clamps `warning`/`critical` upward so the invariant holds regardless
+    /// of which bands were explicitly set vs. independently defaulted.
+    fn normalized(mut self) -> Self {
+        self.warning = self.warning.max(self.info);
+        self.critical = self.critical.max(self.warning);
+        self
+    }
+
+    /// Returns the highest severity `value` breaches, or `None` if it stays
+    /// under even the `info` band.
+    pub fn check(self, value: f64) -> Option<Severity> {
+        if value > self.critical {
+            Some(Severity::Critical)
+        } else if value > self.warning {
+            Some(Severity::Warning)
+        } else if value > self.info {
+            Some(Severity::Info)
+        } else {
+            None
+        }
+    }
+
+    /// The band boundary for a given severity, e.g. for use in alert text.
+    pub fn for_severity(self, sev: Severity) -> f64 {
+        match sev {
+            Severity::Info => self.info,
+            Severity::Warning => self.warning,
+            Severity::Critical => self.critical,
+        }
+    }
+
+    fn apply_override(&mut self, info: Option<f64>, warning: Option<f64>, critical: Option<f64>) {
+        if let Some(v) = info {
+            self.info = v;
+        }
+        if let Some(v) = warning {
+            self.warning = v;
+        }
+        if let Some(v) = critical {
+            self.critical = v;
+        }
+        *self = self.normalized();
+    }
+}
+
+/// The raw, flat `_info`/`_warning`/`_critical` triplet for every metric, as
+/// it appears at the top level of the TOML file (the global defaults).
+///
+/// Kept flat (rather than nested `[wait_pct]` tables) so existing
+/// `metric_warning = ...` style config files need no restructuring.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+struct RawThresholds {
+    wait_pct_info: f64,
+    wait_pct_warning: f64,
+    wait_pct_critical: f64,
+    #[serde(deserialize_with = "crate::units::de_latency_ms")]
+    io_latency_ms_info: f64,
+    #[serde(deserialize_with = "crate::units::de_latency_ms")]
+    io_latency_ms_warning: f64,
+    #[serde(deserialize_with = "crate::units::de_latency_ms")]
+    io_latency_ms_critical: f64,
+    row_lock_pct_info: f64,
+    row_lock_pct_warning: f64,
+    row_lock_pct_critical: f64,
+    gc_remote_pct_info: f64,
+    gc_remote_pct_warning: f64,
+    gc_remote_pct_critical: f64,
+    #[serde(deserialize_with = "crate::units::de_rate")]
+    io_request_rate_info: f64,
+    #[serde(deserialize_with = "crate::units::de_rate")]
+    io_request_rate_warning: f64,
+    #[serde(deserialize_with = "crate::units::de_rate")]
+    io_request_rate_critical: f64,
+
+    // Hysteresis band per metric: once a metric has tripped an alert state,
+    // it only recovers once its value falls past `boundary - hysteresis`,
+    // rather than flipping back to Ok the instant it dips under the
+    // boundary. See `alert_engine::AlertEngine`.
+    wait_pct_hysteresis: f64,
+    #[serde(deserialize_with = "crate::units::de_latency_ms")]
+    io_latency_ms_hysteresis: f64,
+    row_lock_pct_hysteresis: f64,
+    gc_remote_pct_hysteresis: f64,
+    #[serde(deserialize_with = "crate::units::de_rate")]
+    io_request_rate_hysteresis: f64,
+
+    // Minimum consecutive snapshots a metric must hold a state before the
+    // alert engine reports it. 1 (the default) reports immediately, same
+    // as the old stateless behavior.
+    persist: u32,
+
+    // Percent change from first to last snapshot that `trend::analyze`
+    // flags as a regression, for a metric that isn't climbing on *every*
+    // snapshot (monotonic growth always flags, regardless of this value).
+    trend_regression_pct: f64,
+
+    // Exponential bucket boundaries for the `export --format prometheus` latency
+    // histogram: base, base*factor, base*factor^2, ... for `count` buckets.
+    histogram_bucket_base_ms: f64,
+    histogram_bucket_factor: f64,
+    histogram_bucket_count: u32,
+
+    // Per-severity point weights for `health::score`'s aggregate scoring,
+    // multiplied by the category weight below for each emitted alert.
+    severity_weight_info: f64,
+    severity_weight_warning: f64,
+    severity_weight_critical: f64,
+
+    // Per-category point weights for `health::score`, see `health::Category`.
+    category_weight_latency: f64,
+    category_weight_redo: f64,
+    category_weight_racgc: f64,
+    category_weight_concurrency: f64,
+    category_weight_throughput: f64,
+    category_weight_other: f64,
+
+    // Total score at/above which `health::score` returns Degraded / Critical.
+    verdict_degraded_score: f64,
+    verdict_critical_score: f64,
+}
+
+impl Default for RawThresholds {
+    fn default() -> Self {
+        let d = AlertThresholds::default();
+        RawThresholds {
+            wait_pct_info: d.wait_pct.info,
+            wait_pct_warning: d.wait_pct.warning,
+            wait_pct_critical: d.wait_pct.critical,
+            io_latency_ms_info: d.io_latency_ms.info,
+            io_latency_ms_warning: d.io_latency_ms.warning,
+            io_latency_ms_critical: d.io_latency_ms.critical,
+            row_lock_pct_info: d.row_lock_pct.info,
+            row_lock_pct_warning: d.row_lock_pct.warning,
+            row_lock_pct_critical: d.row_lock_pct.critical,
+            gc_remote_pct_info: d.gc_remote_pct.info,
+            gc_remote_pct_warning: d.gc_remote_pct.warning,
+            gc_remote_pct_critical: d.gc_remote_pct.critical,
+            io_request_rate_info: d.io_request_rate.info,
+            io_request_rate_warning: d.io_request_rate.warning,
+            io_request_rate_critical: d.io_request_rate.critical,
+            wait_pct_hysteresis: d.wait_pct_hysteresis,
+            io_latency_ms_hysteresis: d.io_latency_ms_hysteresis,
+            row_lock_pct_hysteresis: d.row_lock_pct_hysteresis,
+            gc_remote_pct_hysteresis: d.gc_remote_pct_hysteresis,
+            io_request_rate_hysteresis: d.io_request_rate_hysteresis,
+            persist: d.persist,
+            trend_regression_pct: d.trend_regression_pct,
+            histogram_bucket_base_ms: d.histogram_bucket_base_ms,
+            histogram_bucket_factor: d.histogram_bucket_factor,
+            histogram_bucket_count: d.histogram_bucket_count,
+            severity_weight_info: d.severity_weight_info,
+            severity_weight_warning: d.severity_weight_warning,
+            severity_weight_critical: d.severity_weight_critical,
+            category_weight_latency: d.category_weight_latency,
+            category_weight_redo: d.category_weight_redo,
+            category_weight_racgc: d.category_weight_racgc,
+            category_weight_concurrency: d.category_weight_concurrency,
+            category_weight_throughput: d.category_weight_throughput,
+            category_weight_other: d.category_weight_other,
+            verdict_degraded_score: d.verdict_degraded_score,
+            verdict_critical_score: d.verdict_critical_score,
+        }
+    }
+}
+
+/// Same shape as `RawThresholds`, but every field is optional so a
+/// `[tablespace.X]` / `[datafile.Y]` table only overrides the bands it
+/// actually mentions, leaving the rest inherited from the globals.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct ThresholdOverride {
+    wait_pct_info: Option<f64>,
+    wait_pct_warning: Option<f64>,
+    wait_pct_critical: Option<f64>,
+    #[serde(default, deserialize_with = "crate::units::de_opt_latency_ms")]
+    io_latency_ms_info: Option<f64>,
+    #[serde(default, deserialize_with = "crate::units::de_opt_latency_ms")]
+    io_latency_ms_warning: Option<f64>,
+    #[serde(default, deserialize_with = "crate::units::de_opt_latency_ms")]
+    io_latency_ms_critical: Option<f64>,
+    row_lock_pct_info: Option<f64>,
+    row_lock_pct_warning: Option<f64>,
+    row_lock_pct_critical: Option<f64>,
+    gc_remote_pct_info: Option<f64>,
+    gc_remote_pct_warning: Option<f64>,
+    gc_remote_pct_critical: Option<f64>,
+    #[serde(default, deserialize_with = "crate::units::de_opt_rate")]
+    io_request_rate_info: Option<f64>,
+    #[serde(default, deserialize_with = "crate::units::de_opt_rate")]
+    io_request_rate_warning: Option<f64>,
+    #[serde(default, deserialize_with = "crate::units::de_opt_rate")]
+    io_request_rate_critical: Option<f64>,
+
+    wait_pct_hysteresis: Option<f64>,
+    #[serde(default, deserialize_with = "crate::units::de_opt_latency_ms")]
+    io_latency_ms_hysteresis: Option<f64>,
+    row_lock_pct_hysteresis: Option<f64>,
+    gc_remote_pct_hysteresis: Option<f64>,
+    #[serde(default, deserialize_with = "crate::units::de_opt_rate")]
+    io_request_rate_hysteresis: Option<f64>,
+
+    persist: Option<u32>,
+    trend_regression_pct: Option<f64>,
+
+    histogram_bucket_base_ms: Option<f64>,
+    histogram_bucket_factor: Option<f64>,
+    histogram_bucket_count: Option<u32>,
+
+    severity_weight_info: Option<f64>,
+    severity_weight_warning: Option<f64>,
+    severity_weight_critical: Option<f64>,
+
+    category_weight_latency: Option<f64>,
+    category_weight_redo: Option<f64>,
+    category_weight_racgc: Option<f64>,
+    category_weight_concurrency: Option<f64>,
+    category_weight_throughput: Option<f64>,
+    category_weight_other: Option<f64>,
+
+    verdict_degraded_score: Option<f64>,
+    verdict_critical_score: Option<f64>,
+}
+
+/// Top-level shape of `awr_io_analyze.toml`: global thresholds at the root,
+/// plus optional per-object override tables keyed by tablespace or
+/// datafile name.
+///
+/// ```toml
+/// wait_pct_warning = 10.0
+/// io_latency_ms_warning = 20.0
+/// io_latency_ms_critical = 100.0
+///
+/// [tablespace.UNDOTBS1]
+/// io_latency_ms_critical = 300.0
+///
+/// [datafile."+DATA/orcl/datafile/system01.dbf"]
+/// io_latency_ms_warning = 40.0
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct ThresholdsFile {
+    #[serde(flatten)]
+    globals: RawThresholds,
+    tablespace: HashMap<String, ThresholdOverride>,
+    datafile: HashMap<String, ThresholdOverride>,
+}
+
 /// All configurable thresholds for AWR analysis.
 /// Add more fields here for new alert types.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AlertThresholds {
-    pub wait_pct: f64,        // % DB Time for waits (default: 10.0)
-    pub io_latency_ms: f64,   // I/O latency in ms (default: 20.0)
-    pub row_lock_pct: f64,    // Row lock contention % (default: 3.0)
-    pub gc_remote_pct: f64,   // GC remote transfer % (default: 2.0)
+    pub wait_pct: Tier,        // % DB Time for waits
+    pub io_latency_ms: Tier,   // I/O latency in ms
+    pub row_lock_pct: Tier,    // Row lock contention %
+    pub gc_remote_pct: Tier,   // GC remote transfer %
     // Add more thresholds below as needed!
-    pub io_request_rate: f64, // i/o request rate from io_profile view
+    pub io_request_rate: Tier, // i/o request rate from io_profile view
+
+    // Hysteresis band per metric, consumed by `alert_engine::AlertEngine`.
+    pub wait_pct_hysteresis: f64,
+    pub io_latency_ms_hysteresis: f64,
+    pub row_lock_pct_hysteresis: f64,
+    pub gc_remote_pct_hysteresis: f64,
+    pub io_request_rate_hysteresis: f64,
+
+    // Minimum consecutive snapshots before the alert engine reports a state.
+    pub persist: u32,
+
+    // Percent growth from first to last snapshot that `trend::analyze`
+    // treats as a regression. See `RawThresholds::trend_regression_pct`.
+    pub trend_regression_pct: f64,
+
+    // Exponential histogram buckets for `prometheus::render`'s latency
+    // histogram. See `RawThresholds::histogram_bucket_base_ms`.
+    pub histogram_bucket_base_ms: f64,
+    pub histogram_bucket_factor: f64,
+    pub histogram_bucket_count: u32,
+
+    // Per-severity/category point weights and verdict cutoffs for
+    // `health::score`'s aggregate scoring. See `health.rs`.
+    pub severity_weight_info: f64,
+    pub severity_weight_warning: f64,
+    pub severity_weight_critical: f64,
+    pub category_weight_latency: f64,
+    pub category_weight_redo: f64,
+    pub category_weight_racgc: f64,
+    pub category_weight_concurrency: f64,
+    pub category_weight_throughput: f64,
+    pub category_weight_other: f64,
+    pub verdict_degraded_score: f64,
+    pub verdict_critical_score: f64,
+
+    // Per-object override tables, keyed by tablespace / datafile name.
+    // Not populated by `Default`; only `load_thresholds_from_file` fills these.
+    // Skipped in JSON output — a report's "active thresholds" means the
+    // resolved globals, not a dump of every per-object override table.
+    #[serde(skip)]
+    tablespace: HashMap<String, ThresholdOverride>,
+    #[serde(skip)]
+    datafile: HashMap<String, ThresholdOverride>,
 }
 
 /// Defaults used if no config file or missing values.
 impl Default for AlertThresholds {
     fn default() -> Self {
         AlertThresholds {
-            wait_pct: 10.0,
-            io_latency_ms: 20.0,
-            row_lock_pct: 3.0,
-            gc_remote_pct: 2.0,
-            io_request_rate: 10_000.0,
+            wait_pct: Tier::new(10.0, 25.0, 50.0),
+            io_latency_ms: Tier::new(20.0, 50.0, 200.0),
+            row_lock_pct: Tier::new(3.0, 8.0, 20.0),
+            gc_remote_pct: Tier::new(2.0, 5.0, 15.0),
+            io_request_rate: Tier::new(10_000.0, 25_000.0, 50_000.0),
+            wait_pct_hysteresis: 2.0,
+            io_latency_ms_hysteresis: 3.0,
+            row_lock_pct_hysteresis: 1.0,
+            gc_remote_pct_hysteresis: 0.5,
+            io_request_rate_hysteresis: 500.0,
+            persist: 1,
+            trend_regression_pct: 50.0,
+            histogram_bucket_base_ms: 1.0,
+            histogram_bucket_factor: 2.0,
+            histogram_bucket_count: 10,
+            severity_weight_info: 1.0,
+            severity_weight_warning: 3.0,
+            severity_weight_critical: 8.0,
+            category_weight_latency: 1.5,
+            category_weight_redo: 1.2,
+            category_weight_racgc: 1.5,
+            category_weight_concurrency: 1.0,
+            category_weight_throughput: 1.0,
+            category_weight_other: 0.5,
+            verdict_degraded_score: 10.0,
+            verdict_critical_score: 30.0,
+            tablespace: HashMap::new(),
+            datafile: HashMap::new(),
+        }
+    }
+}
+
+impl AlertThresholds {
+    fn from_raw(raw: RawThresholds) -> AlertThresholds {
+        AlertThresholds {
+            wait_pct: Tier::new(raw.wait_pct_info, raw.wait_pct_warning, raw.wait_pct_critical),
+            io_latency_ms: Tier::new(raw.io_latency_ms_info, raw.io_latency_ms_warning, raw.io_latency_ms_critical),
+            row_lock_pct: Tier::new(raw.row_lock_pct_info, raw.row_lock_pct_warning, raw.row_lock_pct_critical),
+            gc_remote_pct: Tier::new(raw.gc_remote_pct_info, raw.gc_remote_pct_warning, raw.gc_remote_pct_critical),
+            io_request_rate: Tier::new(
+                raw.io_request_rate_info,
+                raw.io_request_rate_warning,
+                raw.io_request_rate_critical,
+            ),
+            wait_pct_hysteresis: raw.wait_pct_hysteresis,
+            io_latency_ms_hysteresis: raw.io_latency_ms_hysteresis,
+            row_lock_pct_hysteresis: raw.row_lock_pct_hysteresis,
+            gc_remote_pct_hysteresis: raw.gc_remote_pct_hysteresis,
+            io_request_rate_hysteresis: raw.io_request_rate_hysteresis,
+            persist: raw.persist,
+            trend_regression_pct: raw.trend_regression_pct,
+            histogram_bucket_base_ms: raw.histogram_bucket_base_ms,
+            histogram_bucket_factor: raw.histogram_bucket_factor,
+            histogram_bucket_count: raw.histogram_bucket_count,
+            severity_weight_info: raw.severity_weight_info,
+            severity_weight_warning: raw.severity_weight_warning,
+            severity_weight_critical: raw.severity_weight_critical,
+            category_weight_latency: raw.category_weight_latency,
+            category_weight_redo: raw.category_weight_redo,
+            category_weight_racgc: raw.category_weight_racgc,
+            category_weight_concurrency: raw.category_weight_concurrency,
+            category_weight_throughput: raw.category_weight_throughput,
+            category_weight_other: raw.category_weight_other,
+            verdict_degraded_score: raw.verdict_degraded_score,
+            verdict_critical_score: raw.verdict_critical_score,
+            tablespace: HashMap::new(),
+            datafile: HashMap::new(),
+        }
+    }
+
+    fn apply_override(&mut self, ov: &ThresholdOverride) {
+        self.wait_pct.apply_override(ov.wait_pct_info, ov.wait_pct_warning, ov.wait_pct_critical);
+        self.io_latency_ms.apply_override(ov.io_latency_ms_info, ov.io_latency_ms_warning, ov.io_latency_ms_critical);
+        self.row_lock_pct.apply_override(ov.row_lock_pct_info, ov.row_lock_pct_warning, ov.row_lock_pct_critical);
+        self.gc_remote_pct.apply_override(ov.gc_remote_pct_info, ov.gc_remote_pct_warning, ov.gc_remote_pct_critical);
+        self.io_request_rate.apply_override(
+            ov.io_request_rate_info,
+            ov.io_request_rate_warning,
+            ov.io_request_rate_critical,
+        );
+        if let Some(v) = ov.wait_pct_hysteresis {
+            self.wait_pct_hysteresis = v;
+        }
+        if let Some(v) = ov.io_latency_ms_hysteresis {
+            self.io_latency_ms_hysteresis = v;
+        }
+        if let Some(v) = ov.row_lock_pct_hysteresis {
+            self.row_lock_pct_hysteresis = v;
+        }
+        if let Some(v) = ov.gc_remote_pct_hysteresis {
+            self.gc_remote_pct_hysteresis = v;
+        }
+        if let Some(v) = ov.io_request_rate_hysteresis {
+            self.io_request_rate_hysteresis = v;
+        }
+        if let Some(v) = ov.persist {
+            self.persist = v;
+        }
+        if let Some(v) = ov.trend_regression_pct {
+            self.trend_regression_pct = v;
+        }
+        if let Some(v) = ov.histogram_bucket_base_ms {
+            self.histogram_bucket_base_ms = v;
+        }
+        if let Some(v) = ov.histogram_bucket_factor {
+            self.histogram_bucket_factor = v;
+        }
+        if let Some(v) = ov.histogram_bucket_count {
+            self.histogram_bucket_count = v;
+        }
+        if let Some(v) = ov.severity_weight_info {
+            self.severity_weight_info = v;
+        }
+        if let Some(v) = ov.severity_weight_warning {
+            self.severity_weight_warning = v;
+        }
+        if let Some(v) = ov.severity_weight_critical {
+            self.severity_weight_critical = v;
+        }
+        if let Some(v) = ov.category_weight_latency {
+            self.category_weight_latency = v;
+        }
+        if let Some(v) = ov.category_weight_redo {
+            self.category_weight_redo = v;
+        }
+        if let Some(v) = ov.category_weight_racgc {
+            self.category_weight_racgc = v;
+        }
+        if let Some(v) = ov.category_weight_concurrency {
+            self.category_weight_concurrency = v;
+        }
+        if let Some(v) = ov.category_weight_throughput {
+            self.category_weight_throughput = v;
+        }
+        if let Some(v) = ov.category_weight_other {
+            self.category_weight_other = v;
+        }
+        if let Some(v) = ov.verdict_degraded_score {
+            self.verdict_degraded_score = v;
+        }
+        if let Some(v) = ov.verdict_critical_score {
+            self.verdict_critical_score = v;
+        }
+    }
+
+    /// Returns the effective thresholds for a named tablespace or datafile,
+    /// merging any matching `[tablespace.NAME]` / `[datafile.NAME]` table
+    /// over the global defaults. Objects with no matching table just get
+    /// the globals back unchanged.
+    ///
+    /// Many AWR I/O problems are localized to one tablespace or ASM disk
+    /// group, so a single global latency limit is often too noisy for busy
+    /// objects and too lax for quiet ones — this lets callers tune per object.
+    pub fn for_object(&self, name: &str) -> AlertThresholds {
+        let mut merged = self.clone();
+        if let Some(ov) = self.tablespace.get(name).or_else(|| self.datafile.get(name)) {
+            merged.apply_override(ov);
+        }
+        merged
+    }
+}
+
+/// Why `try_load_thresholds_from_file` couldn't produce a fresh
+/// `AlertThresholds`. Split out (rather than a plain `String`) so callers
+/// like `ThresholdSource` can decide whether a missing file is worth
+/// warning about.
+#[derive(Debug)]
+pub enum LoadError {
+    Unreadable,
+    Parse(String),
+}
+
+/// Top-level keys `ThresholdsFile`/`RawThresholds` recognize. `#[serde(flatten)]`
+/// silently drops anything else instead of erroring (serde's
+/// `deny_unknown_fields` does not work alongside a flattened field), so
+/// `warn_on_unknown_keys` does its own pass to catch typos and pre-tiered
+/// configs still using the old flat scalar keys (e.g. `io_latency_ms = 20.0`
+/// instead of `io_latency_ms_warning` / `io_latency_ms_critical`).
+const KNOWN_KEYS: &[&str] = &[
+    "wait_pct_info", "wait_pct_warning", "wait_pct_critical",
+    "io_latency_ms_info", "io_latency_ms_warning", "io_latency_ms_critical",
+    "row_lock_pct_info", "row_lock_pct_warning", "row_lock_pct_critical",
+    "gc_remote_pct_info", "gc_remote_pct_warning", "gc_remote_pct_critical",
+    "io_request_rate_info", "io_request_rate_warning", "io_request_rate_critical",
+    "wait_pct_hysteresis", "io_latency_ms_hysteresis", "row_lock_pct_hysteresis",
+    "gc_remote_pct_hysteresis", "io_request_rate_hysteresis",
+    "persist", "trend_regression_pct",
+    "histogram_bucket_base_ms", "histogram_bucket_factor", "histogram_bucket_count",
+    "severity_weight_info", "severity_weight_warning", "severity_weight_critical",
+    "category_weight_latency", "category_weight_redo", "category_weight_racgc",
+    "category_weight_concurrency", "category_weight_throughput", "category_weight_other",
+    "verdict_degraded_score", "verdict_critical_score",
+    "tablespace", "datafile",
+];
+
+fn warn_on_unknown_keys(path: &str, contents: &str) {
+    let Ok(toml::Value::Table(table)) = contents.parse::<toml::Value>() else {
+        return;
+    };
+    for key in table.keys() {
+        if !KNOWN_KEYS.contains(&key.as_str()) {
+            eprintln!(
+                "warning: '{}' has unrecognized key '{}' — ignored. \
+                 (old flat scalar thresholds like `{}` = ... must be split into \
+                 `{}_info` / `{}_warning` / `{}_critical`)",
+                path, key, key, key, key, key
+            );
         }
     }
 }
 
+/// Loads thresholds from a TOML config file, returning why on failure
+/// instead of silently falling back — used by `load_thresholds_from_file`
+/// for the one-shot CLI path and by `ThresholdSource` for hot-reload, where
+/// a failed reload should keep serving the previous good values.
+pub fn try_load_thresholds_from_file(path: &str) -> Result<AlertThresholds, LoadError> {
+    let contents = fs::read_to_string(path).map_err(|_| LoadError::Unreadable)?;
+    warn_on_unknown_keys(path, &contents);
+    let file: ThresholdsFile = toml::from_str(&contents).map_err(|e| LoadError::Parse(e.to_string()))?;
+
+    let mut t = AlertThresholds::from_raw(file.globals);
+    t.tablespace = file.tablespace;
+    t.datafile = file.datafile;
+    Ok(t)
+}
+
+/// Builds a fully-commented default `awr_io_analyze.toml`, generated from
+/// `AlertThresholds::default()` so it can never drift from the values the
+/// binary actually falls back to. Used by `awr_io_analyze thresholds init`
+/// so a user can start from a working file instead of hand-building one.
+pub fn default_config_toml() -> String {
+    let d = AlertThresholds::default();
+    format!(
+        "# awr_io_analyze.toml — threshold config for AWR I/O Analyzer (TOML)\n\
+         # Each metric supports graded info/warning/critical bands. Latency and\n\
+         # rate fields also accept human-friendly units instead of bare numbers:\n\
+         # io_latency_ms_* takes \"20ms\" / \"1.5s\" / \"2us\"; io_request_rate_*\n\
+         # takes \"10k\" / \"1.2M\". Bare floats still work unchanged.\n\
+         wait_pct_info = {wait_pct_info}\n\
+         wait_pct_warning = {wait_pct_warning}\n\
+         wait_pct_critical = {wait_pct_critical}\n\
+         io_latency_ms_info = {io_latency_ms_info}\n\
+         io_latency_ms_warning = {io_latency_ms_warning}\n\
+         io_latency_ms_critical = {io_latency_ms_critical}\n\
+         row_lock_pct_info = {row_lock_pct_info}\n\
+         row_lock_pct_warning = {row_lock_pct_warning}\n\
+         row_lock_pct_critical = {row_lock_pct_critical}\n\
+         gc_remote_pct_info = {gc_remote_pct_info}\n\
+         gc_remote_pct_warning = {gc_remote_pct_warning}\n\
+         gc_remote_pct_critical = {gc_remote_pct_critical}\n\
+         io_request_rate_info = {io_request_rate_info}\n\
+         io_request_rate_warning = {io_request_rate_warning}\n\
+         io_request_rate_critical = {io_request_rate_critical}\n\
+         \n\
+         # Optional per-object overrides, merged over the globals above:\n\
+         # [tablespace.UNDOTBS1]\n\
+         # io_latency_ms_critical = 300.0\n\
+         #\n\
+         # [datafile.\"+DATA/orcl/datafile/system01.dbf\"]\n\
+         # io_latency_ms_warning = 40.0\n\
+         \n\
+         # Hysteresis band per metric for multi-snapshot/trend callers\n\
+         # (see alert_engine.rs) — unused by a single-snapshot run.\n\
+         wait_pct_hysteresis = {wait_pct_hysteresis}\n\
+         io_latency_ms_hysteresis = {io_latency_ms_hysteresis}\n\
+         row_lock_pct_hysteresis = {row_lock_pct_hysteresis}\n\
+         gc_remote_pct_hysteresis = {gc_remote_pct_hysteresis}\n\
+         io_request_rate_hysteresis = {io_request_rate_hysteresis}\n\
+         \n\
+         # Minimum consecutive snapshots a metric must hold a state before\n\
+         # the alert engine reports it.\n\
+         persist = {persist}\n\
+         \n\
+         # Percent growth from first to last snapshot that `diff` flags as\n\
+         # a regression (a metric climbing on every snapshot always flags).\n\
+         trend_regression_pct = {trend_regression_pct}\n\
+         \n\
+         # Exponential bucket boundaries for the `export --format\n\
+         # prometheus` latency histogram: base, base*factor, base*factor^2,\n\
+         # ... for `count` buckets.\n\
+         histogram_bucket_base_ms = {histogram_bucket_base_ms}\n\
+         histogram_bucket_factor = {histogram_bucket_factor}\n\
+         histogram_bucket_count = {histogram_bucket_count}\n\
+         \n\
+         # Aggregate health scoring (see health.rs): each alert earns\n\
+         # severity_weight_* * category_weight_* points; the sum across a\n\
+         # report's sections is compared against verdict_degraded_score /\n\
+         # verdict_critical_score for an overall Healthy/Degraded/Critical\n\
+         # verdict.\n\
+         severity_weight_info = {severity_weight_info}\n\
+         severity_weight_warning = {severity_weight_warning}\n\
+         severity_weight_critical = {severity_weight_critical}\n\
+         category_weight_latency = {category_weight_latency}\n\
+         category_weight_redo = {category_weight_redo}\n\
+         category_weight_racgc = {category_weight_racgc}\n\
+         category_weight_concurrency = {category_weight_concurrency}\n\
+         category_weight_throughput = {category_weight_throughput}\n\
+         category_weight_other = {category_weight_other}\n\
+         verdict_degraded_score = {verdict_degraded_score}\n\
+         verdict_critical_score = {verdict_critical_score}\n",
+        wait_pct_info = d.wait_pct.info,
+        wait_pct_warning = d.wait_pct.warning,
+        wait_pct_critical = d.wait_pct.critical,
+        io_latency_ms_info = d.io_latency_ms.info,
+        io_latency_ms_warning = d.io_latency_ms.warning,
+        io_latency_ms_critical = d.io_latency_ms.critical,
+        row_lock_pct_info = d.row_lock_pct.info,
+        row_lock_pct_warning = d.row_lock_pct.warning,
+        row_lock_pct_critical = d.row_lock_pct.critical,
+        gc_remote_pct_info = d.gc_remote_pct.info,
+        gc_remote_pct_warning = d.gc_remote_pct.warning,
+        gc_remote_pct_critical = d.gc_remote_pct.critical,
+        io_request_rate_info = d.io_request_rate.info,
+        io_request_rate_warning = d.io_request_rate.warning,
+        io_request_rate_critical = d.io_request_rate.critical,
+        wait_pct_hysteresis = d.wait_pct_hysteresis,
+        io_latency_ms_hysteresis = d.io_latency_ms_hysteresis,
+        row_lock_pct_hysteresis = d.row_lock_pct_hysteresis,
+        gc_remote_pct_hysteresis = d.gc_remote_pct_hysteresis,
+        io_request_rate_hysteresis = d.io_request_rate_hysteresis,
+        persist = d.persist,
+        trend_regression_pct = d.trend_regression_pct,
+        histogram_bucket_base_ms = d.histogram_bucket_base_ms,
+        histogram_bucket_factor = d.histogram_bucket_factor,
+        histogram_bucket_count = d.histogram_bucket_count,
+        severity_weight_info = d.severity_weight_info,
+        severity_weight_warning = d.severity_weight_warning,
+        severity_weight_critical = d.severity_weight_critical,
+        category_weight_latency = d.category_weight_latency,
+        category_weight_redo = d.category_weight_redo,
+        category_weight_racgc = d.category_weight_racgc,
+        category_weight_concurrency = d.category_weight_concurrency,
+        category_weight_throughput = d.category_weight_throughput,
+        category_weight_other = d.category_weight_other,
+        verdict_degraded_score = d.verdict_degraded_score,
+        verdict_critical_score = d.verdict_critical_score,
+    )
+}
+
 /// Loads thresholds from a TOML config file (if present).
-/// Falls back to defaults for missing keys or missing file.
-///
-/// Example TOML:
-/// ```toml
-/// wait_pct = 10.0
-/// io_latency_ms = 20.0
-/// row_lock_pct = 3.0
-/// gc_remote_pct = 2.0
-/// ```
+/// Falls back to defaults for missing keys, an unreadable file, or a file
+/// that fails to parse (logging a warning in the parse-failure case).
 pub fn load_thresholds_from_file(path: &str) -> AlertThresholds {
-    let contents = fs::read_to_string(path);
-    let mut t = AlertThresholds::default();
-
-    if let Ok(data) = contents {
-        for line in data.lines() {
-            let line = line.trim();
-            if line.starts_with('#') || line.is_empty() {
-                continue;
-            }
-            let parts: Vec<&str> = line.split('=').map(|s| s.trim()).collect();
-            if parts.len() != 2 { continue; }
-
-            match parts[0] {
-                "wait_pct" =>    t.wait_pct = parts[1].parse().unwrap_or(t.wait_pct),
-                "io_latency_ms" => t.io_latency_ms = parts[1].parse().unwrap_or(t.io_latency_ms),
-                "row_lock_pct" => t.row_lock_pct = parts[1].parse().unwrap_or(t.row_lock_pct),
-                "gc_remote_pct" => t.gc_remote_pct = parts[1].parse().unwrap_or(t.gc_remote_pct),
-                "io_request_rate" => t.io_request_rate = parts[1].parse().unwrap_or(t.io_request_rate),
-                _ => {},
-            }
+    match try_load_thresholds_from_file(path) {
+        Ok(t) => t,
+        Err(LoadError::Unreadable) => AlertThresholds::default(),
+        Err(LoadError::Parse(e)) => {
+            eprintln!("warning: failed to parse '{}', using defaults: {}", path, e);
+            AlertThresholds::default()
         }
     }
-    t
 }
 
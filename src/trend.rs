@@ -0,0 +1,272 @@
+/*!
+ * trend.rs — Multi-snapshot trend analysis
+ *
+ * `diff rpt_0900.txt rpt_1000.txt rpt_1100.txt` runs the same
+ * Foreground Wait Events / IO Profile extraction a single-snapshot run
+ * uses, across every file in the list, and aligns each reading by its
+ * normalized event name (`extract_event_name`) or IO Profile label
+ * instead of analyzing each file in isolation. A metric present at every
+ * snapshot that either climbs on every step or grows by more than
+ * `trend_regression_pct` from first to last gets flagged here, with the
+ * full series shown so a DBA can see e.g. `db file sequential read`
+ * latency climbing 4ms → 9ms → 21ms across the window.
+ *
+ * Metrics missing from any snapshot in the window are skipped — there's
+ * no well-defined trend without a reading at every point.
+ *
+ * Alongside that growth check, every reading (gaps included) is also fed
+ * through an `alert_engine::AlertEngine`, one per metric/event pair, so a
+ * metric that rode just above its threshold for the whole window — but
+ * wasn't climbing — still gets reported once as a sustained alert, with
+ * hysteresis+persist suppressing single-snapshot blips. See
+ * `sustained_alerts`.
+ *
+ * Co-developed by Laurence Oberman and ChatGPT (OpenAI), 2025.
+ * License: GPLv3+
+ */
+
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::alert_engine::{AlertEngine, AlertEvent};
+use crate::report::{Alert, Section};
+use crate::thresholds::{AlertThresholds, Severity, Tier};
+use crate::{extract_event_name, extract_latency_ms, extract_native_table, extract_percent_from_wait_row};
+
+/// [RULE 26] A metric trending upward across a `diff` snapshot window.
+const TREND_REGRESSION_RULE: u32 = 26;
+
+/// [RULE 27] A metric that held a hysteresis+persist-confirmed alert (or
+/// graduation/recovery) state across the snapshot window — see `AlertEngine`.
+const SUSTAINED_ALERT_RULE: u32 = 27;
+
+/// First numeric value on a line mentioning `needle`, from an already
+/// extracted IO Profile table — same "grab the first number" approach
+/// `alert_on_io_profile` uses for its rate/MB-per-sec fields.
+fn io_profile_value(table: &[String], needle: &str) -> Option<f64> {
+    let num_re = Regex::new(r"(\d[\d,\.]+)").unwrap();
+    table
+        .iter()
+        .find(|l| l.contains(needle))
+        .and_then(|l| num_re.captures(l).and_then(|c| c[1].replace(',', "").parse().ok()))
+}
+
+/// Keeps a series only if every snapshot has a reading for it — a metric
+/// that only showed up in some reports doesn't have a well-defined trend.
+fn complete(series: &[Option<f64>]) -> Option<Vec<f64>> {
+    series.iter().cloned().collect()
+}
+
+/// Flags one metric's series as a regression if it climbs on every
+/// snapshot, or grows by more than `regression_pct` from first to last.
+fn check_series(label: &str, unit: &str, values: &[f64], regression_pct: f64) -> Option<Alert> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let monotonic = values.windows(2).all(|w| w[1] > w[0]);
+    let first = values[0];
+    let last = *values.last().unwrap();
+    let change_pct = if first != 0.0 {
+        (last - first) / first * 100.0
+    } else if last > 0.0 {
+        f64::INFINITY
+    } else {
+        0.0
+    };
+
+    if !monotonic && change_pct <= regression_pct {
+        return None;
+    }
+
+    let series_str = values.iter().map(|v| format!("{:.2}{}", v, unit)).collect::<Vec<_>>().join(" → ");
+    let reason = if monotonic {
+        "climbing on every snapshot".to_string()
+    } else {
+        format!("up {:.1}% from first to last", change_pct)
+    };
+    let text = format!(
+        "🟠 WARNING: '{}' trending upward across {} snapshots ({}): {}.",
+        label,
+        values.len(),
+        series_str,
+        reason
+    );
+    Some(Alert::new(TREND_REGRESSION_RULE, Severity::Warning, label, Some(last), Some(first), text))
+}
+
+/// The per-metric pieces `sustained_alerts` needs from `AlertThresholds` —
+/// grouped into one argument purely to keep the function's arg count sane;
+/// `tier`/`hysteresis` always come from the same named field of
+/// `AlertThresholds` as `name`.
+struct SustainedMetric<'a> {
+    name: &'a str,
+    unit: &'a str,
+    tier: Tier,
+    hysteresis: f64,
+}
+
+/// Feeds one metric's per-snapshot readings (oldest first, gaps skipped)
+/// through `engine` and turns any reported Entered/Recovered transition
+/// into an `Alert` — the hysteresis+persist-smoothed counterpart to
+/// `check_series`'s plain growth check, so a metric that merely blips over
+/// threshold for one snapshot doesn't get reported as "sustained".
+fn sustained_alerts(
+    engine: &mut AlertEngine,
+    metric: &SustainedMetric,
+    event: &str,
+    series: &[Option<f64>],
+    persist: u32,
+) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+    for value in series.iter().flatten() {
+        if let Some(event_transition) =
+            engine.evaluate(metric.name, event, *value, metric.tier, metric.hysteresis, persist)
+        {
+            let alert = match event_transition {
+                AlertEvent::Entered { metric: name, object, severity, value } => {
+                    let text = format!(
+                        "{} {}: '{}' ({}) held this state for {} consecutive snapshots at {:.2}{} (> {:.1}{} threshold).",
+                        severity.emoji(),
+                        severity.label(),
+                        object,
+                        name,
+                        persist.max(1),
+                        value,
+                        metric.unit,
+                        metric.tier.for_severity(severity),
+                        metric.unit
+                    );
+                    Alert::new(
+                        SUSTAINED_ALERT_RULE,
+                        severity,
+                        object,
+                        Some(value),
+                        Some(metric.tier.for_severity(severity)),
+                        text,
+                    )
+                }
+                AlertEvent::Recovered { metric: name, object, value } => {
+                    let text = format!(
+                        "🔵 INFO: '{}' ({}) recovered to {:.2}{}, state held.",
+                        object, name, value, metric.unit
+                    );
+                    Alert::new(SUSTAINED_ALERT_RULE, Severity::Info, object, Some(value), None, text)
+                }
+            };
+            alerts.push(alert);
+        }
+    }
+    alerts
+}
+
+/// Runs the Foreground Wait Events and IO Profile extraction across every
+/// `(label, lines)` snapshot, in the order given (oldest first), and
+/// returns a "Trend / Regression" section flagging metrics that grow
+/// across the window per `thresholds.trend_regression_pct`.
+pub fn analyze(snapshots: &[(String, Vec<String>)], thresholds: &AlertThresholds) -> Section {
+    let n = snapshots.len();
+    let mut latency: HashMap<String, Vec<Option<f64>>> = HashMap::new();
+    let mut wait_pct: HashMap<String, Vec<Option<f64>>> = HashMap::new();
+    let mut io_metrics: HashMap<&'static str, Vec<Option<f64>>> = HashMap::new();
+
+    for (idx, (_, lines)) in snapshots.iter().enumerate() {
+        if let Some(table) = extract_native_table(lines, r"Top 10 Foreground Events by Total Wait Time", 2) {
+            for row in &table {
+                let event = extract_event_name(row);
+                if let Some(lat) = extract_latency_ms(row) {
+                    latency.entry(event.clone()).or_insert_with(|| vec![None; n])[idx] = Some(lat);
+                }
+                if let Some(pct) = extract_percent_from_wait_row(row) {
+                    wait_pct.entry(event).or_insert_with(|| vec![None; n])[idx] = Some(pct);
+                }
+            }
+        }
+
+        if let Some(table) = extract_native_table(lines, r"IO Profile", 2) {
+            for (label, needle) in [
+                ("io_request_rate", "Total Requests:"),
+                ("read_mb_sec", "Read MB/sec"),
+                ("write_mb_sec", "Write MB/sec"),
+            ] {
+                if let Some(v) = io_profile_value(&table, needle) {
+                    io_metrics.entry(label).or_insert_with(|| vec![None; n])[idx] = Some(v);
+                }
+            }
+        }
+    }
+
+    let mut alerts = Vec::new();
+    for (event, series) in &latency {
+        if let Some(values) = complete(series) {
+            let label = format!("{} (latency)", event);
+            if let Some(a) = check_series(&label, "ms", &values, thresholds.trend_regression_pct) {
+                alerts.push(a);
+            }
+        }
+    }
+    for (event, series) in &wait_pct {
+        if let Some(values) = complete(series) {
+            let label = format!("{} (% DB time)", event);
+            if let Some(a) = check_series(&label, "%", &values, thresholds.trend_regression_pct) {
+                alerts.push(a);
+            }
+        }
+    }
+    for (label, series) in &io_metrics {
+        if let Some(values) = complete(series) {
+            if let Some(a) = check_series(label, "", &values, thresholds.trend_regression_pct) {
+                alerts.push(a);
+            }
+        }
+    }
+
+    // Hysteresis+persist-smoothed sustained-state alerts, across the same
+    // series, via `AlertEngine` — catches a metric riding just above
+    // threshold the whole window even when it's not "climbing" as
+    // `check_series` requires.
+    let mut engine = AlertEngine::new();
+    let io_latency_ms_metric = SustainedMetric {
+        name: "io_latency_ms",
+        unit: "ms",
+        tier: thresholds.io_latency_ms,
+        hysteresis: thresholds.io_latency_ms_hysteresis,
+    };
+    for (event, series) in &latency {
+        alerts.extend(sustained_alerts(&mut engine, &io_latency_ms_metric, event, series, thresholds.persist));
+    }
+    let wait_pct_metric = SustainedMetric {
+        name: "wait_pct",
+        unit: "%",
+        tier: thresholds.wait_pct,
+        hysteresis: thresholds.wait_pct_hysteresis,
+    };
+    for (event, series) in &wait_pct {
+        alerts.extend(sustained_alerts(&mut engine, &wait_pct_metric, event, series, thresholds.persist));
+    }
+    if let Some(series) = io_metrics.get("io_request_rate") {
+        let io_request_rate_metric = SustainedMetric {
+            name: "io_request_rate",
+            unit: "",
+            tier: thresholds.io_request_rate,
+            hysteresis: thresholds.io_request_rate_hysteresis,
+        };
+        alerts.extend(sustained_alerts(
+            &mut engine,
+            &io_request_rate_metric,
+            "io_request_rate",
+            series,
+            thresholds.persist,
+        ));
+    }
+
+    alerts.sort_by(|a, b| a.event.cmp(&b.event));
+
+    Section {
+        name: "Trend / Regression".to_string(),
+        found: !snapshots.is_empty(),
+        rows: snapshots.iter().map(|(label, _)| label.clone()).collect(),
+        alerts,
+    }
+}
@@ -0,0 +1,101 @@
+/*!
+ * units.rs — Human-friendly unit/duration parsing for threshold config values
+ *
+ * Lets awr_io_analyze.toml say `io_latency_ms_critical = "200ms"` or
+ * `io_request_rate_warning = "25k"` instead of a bare float — "how many
+ * zeros is 10000" is a real foot-gun for I/O rate thresholds. Bare
+ * floats/ints are still accepted unchanged.
+ *
+ * Co-developed by Laurence Oberman and ChatGPT (OpenAI), 2025.
+ * License: GPLv3+
+ */
+
+use serde::{Deserialize, Deserializer};
+
+/// What a threshold field measures, so its unit suffixes are unambiguous.
+#[derive(Clone, Copy)]
+pub enum Unit {
+    /// Canonical value stored in milliseconds: `"20ms"`, `"1.5s"`, `"2us"`.
+    MillisDuration,
+    /// Canonical value stored in requests/sec or any plain count: `"10k"`, `"1.2M"`.
+    Rate,
+}
+
+/// Parses a human-friendly threshold value into its canonical numeric form
+/// for `unit`. Falls back to a plain float parse when there's no
+/// recognized unit suffix.
+pub fn parse(raw: &str, unit: Unit) -> Option<f64> {
+    let raw = raw.trim();
+
+    if let Ok(v) = raw.parse::<f64>() {
+        return Some(v);
+    }
+
+    let split_at = raw.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (num, suffix) = raw.split_at(split_at);
+    let num: f64 = num.trim().parse().ok()?;
+    let suffix = suffix.trim();
+
+    match unit {
+        Unit::MillisDuration => match suffix {
+            "ms" => Some(num),
+            "s" => Some(num * 1_000.0),
+            "us" | "µs" => Some(num / 1_000.0),
+            _ => None,
+        },
+        Unit::Rate => match suffix {
+            "k" | "K" => Some(num * 1_000.0),
+            "M" => Some(num * 1_000_000.0),
+            "G" => Some(num * 1_000_000_000.0),
+            _ => None,
+        },
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumOrString {
+    Num(f64),
+    Str(String),
+}
+
+fn resolve(v: NumOrString, unit: Unit) -> Result<f64, String> {
+    match v {
+        NumOrString::Num(n) => Ok(n),
+        NumOrString::Str(s) => {
+            parse(&s, unit).ok_or_else(|| format!("unrecognized threshold value '{}'", s))
+        }
+    }
+}
+
+pub fn de_latency_ms<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let v = NumOrString::deserialize(deserializer)?;
+    resolve(v, Unit::MillisDuration).map_err(serde::de::Error::custom)
+}
+
+pub fn de_rate<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let v = NumOrString::deserialize(deserializer)?;
+    resolve(v, Unit::Rate).map_err(serde::de::Error::custom)
+}
+
+pub fn de_opt_latency_ms<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let v = Option::<NumOrString>::deserialize(deserializer)?;
+    v.map(|v| resolve(v, Unit::MillisDuration)).transpose().map_err(serde::de::Error::custom)
+}
+
+pub fn de_opt_rate<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let v = Option::<NumOrString>::deserialize(deserializer)?;
+    v.map(|v| resolve(v, Unit::Rate)).transpose().map_err(serde::de::Error::custom)
+}